@@ -2,6 +2,11 @@
 // Parses and manages the .orlint.toml configuration file
 
 use crate::reporter::Severity;
+use crate::rules::{
+    DeclarativeRule, DesignTokensRule, EventHandlerNamingRule, MethodNamingRule, PropNamingRule,
+    StateNamingRule, UndefinedClassRule,
+};
+use crate::AnalyzerError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -26,6 +31,10 @@ pub struct Config {
     #[serde(default)]
     pub renderer_analysis: RendererAnalysisConfig,
 
+    /// Rule plugins to load
+    #[serde(default)]
+    pub plugins: Vec<PluginConfig>,
+
     /// Rule severity levels (flattened into rules.rule_severity during deserialization)
     #[serde(default, rename = "rule_severity")]
     rule_severity_tmp: HashMap<String, Severity>,
@@ -73,6 +82,235 @@ pub struct RulesConfig {
     /// Custom severity levels for rules
     #[serde(default)]
     pub rule_severity: HashMap<String, Severity>,
+
+    /// Declarative rules defined in the configuration file
+    #[serde(default)]
+    pub custom: Vec<CustomRuleConfig>,
+
+    /// `event-handler-naming` rule configuration
+    #[serde(default, rename = "event-handler-naming")]
+    pub event_handler_naming: EventHandlerNamingConfig,
+
+    /// `prop-naming` rule configuration
+    #[serde(default, rename = "prop-naming")]
+    pub prop_naming: MemberNamingConfig,
+
+    /// `state-naming` rule configuration
+    #[serde(default, rename = "state-naming")]
+    pub state_naming: MemberNamingConfig,
+
+    /// `method-naming` rule configuration
+    #[serde(default, rename = "method-naming")]
+    pub method_naming: MemberNamingConfig,
+
+    /// `lifecycle-method` rule configuration
+    #[serde(default, rename = "lifecycle-method")]
+    pub lifecycle_method: LifecycleConfig,
+
+    /// `valid-type-annotation` rule configuration
+    #[serde(default, rename = "valid-type-annotation")]
+    pub valid_type_annotation: TypeAnnotationConfig,
+
+    /// `undefined-class` rule configuration
+    #[serde(default, rename = "undefined-class")]
+    pub undefined_class: UndefinedClassConfig,
+
+    /// `unscoped-selector` rule configuration
+    #[serde(default, rename = "unscoped-selector")]
+    pub unscoped_selector: UnscopedSelectorConfig,
+
+    /// `unknown-property` rule configuration
+    #[serde(default, rename = "unknown-property")]
+    pub unknown_property: UnknownPropertyConfig,
+
+    /// `design-tokens` rule configuration
+    #[serde(default, rename = "design-tokens")]
+    pub design_tokens: DesignTokensConfig,
+
+    /// Accessibility rule configuration
+    #[serde(default)]
+    pub accessibility: AccessibilityConfig,
+}
+
+/// `event-handler-naming` rule configuration
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EventHandlerNamingConfig {
+    /// Regex for methods bound to template events
+    #[serde(default = "default_handler_pattern")]
+    pub handler_pattern: String,
+
+    /// Regex for props with a function type
+    #[serde(default = "default_callback_prop_pattern")]
+    pub callback_prop_pattern: String,
+}
+
+/// `prop-naming`, `state-naming` and `method-naming` rule configuration
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemberNamingConfig {
+    /// Regex for member names
+    #[serde(default = "default_member_pattern")]
+    pub pattern: String,
+
+    /// Regex for boolean props and state variables; empty to disable (not used for methods)
+    #[serde(default = "default_boolean_pattern")]
+    pub boolean_pattern: String,
+}
+
+/// `lifecycle-method` rule configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LifecycleConfig {
+    /// Report components that don't implement any lifecycle hook
+    #[serde(default)]
+    pub require_hook: bool,
+}
+
+/// Accessibility (`a11y-*`) rule configuration
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccessibilityConfig {
+    /// Check images for alt text (`a11y-missing-alt`)
+    #[serde(default = "default_true")]
+    pub check_alt_attributes: bool,
+
+    /// Check `role` values and the ARIA attributes allowed for each role
+    /// (`a11y-valid-role`, `a11y-role-attributes`)
+    #[serde(default = "default_true")]
+    pub check_aria_roles: bool,
+
+    /// Check click handlers, tabindex, autofocus and focusable hidden content
+    /// (`a11y-click-keyboard`, `a11y-positive-tabindex`, `a11y-no-autofocus`, `a11y-hidden-focusable`)
+    #[serde(default = "default_true")]
+    pub check_keyboard_navigation: bool,
+
+    /// Check text and background colors resolved from the style section (`a11y-color-contrast`)
+    #[serde(default = "default_true")]
+    pub check_color_contrast: bool,
+
+    /// WCAG level whose minimum contrast ratios are enforced
+    #[serde(default)]
+    pub wcag_level: WcagLevel,
+
+    /// Check heading order, and a single `<h1>` and a main landmark in pages
+    /// (`a11y-heading-order`, `a11y-single-h1`, `a11y-page-landmarks`)
+    #[serde(default = "default_true")]
+    pub check_page_structure: bool,
+
+    /// Glob patterns for page-level components, in addition to those with `<page>` metadata
+    #[serde(default)]
+    pub pages: Vec<String>,
+}
+
+/// WCAG conformance level
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WcagLevel {
+    /// Level AA: 4.5:1 for normal text, 3:1 for large text
+    #[default]
+    #[serde(rename = "AA", alias = "aa")]
+    Aa,
+    /// Level AAA: 7:1 for normal text, 4.5:1 for large text
+    #[serde(rename = "AAA", alias = "aaa")]
+    Aaa,
+}
+
+/// `valid-type-annotation` rule configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TypeAnnotationConfig {
+    /// Type names to accept in addition to the built-in types and the types declared or
+    /// imported in the component's script section
+    #[serde(default)]
+    pub known_types: Vec<String>,
+}
+
+/// `undefined-class` rule configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UndefinedClassConfig {
+    /// Regexes for classes defined outside the component, such as utility or global classes
+    #[serde(default)]
+    pub ignore: Vec<String>,
+}
+
+/// `unscoped-selector` rule configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UnscopedSelectorConfig {
+    /// Whether the project scopes component styles to the component, so element selectors
+    /// don't leak; `:root`, `html` and `body` selectors are still reported
+    #[serde(default)]
+    pub scoped: bool,
+}
+
+/// `unknown-property` rule configuration
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UnknownPropertyConfig {
+    /// Properties to accept in addition to the built-in list, such as renderer extensions
+    #[serde(default)]
+    pub known_properties: Vec<String>,
+}
+
+/// `design-tokens` rule configuration
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DesignTokensConfig {
+    /// JSON or TOML file with the design tokens, relative to the configuration file; the rule
+    /// is disabled without one
+    #[serde(default)]
+    pub tokens_file: Option<String>,
+
+    /// How styles reference a token; `{name}` is the token's path joined with `-`
+    #[serde(default = "default_token_reference")]
+    pub reference: String,
+}
+
+/// Declarative rule defined in the configuration file
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomRuleConfig {
+    /// Rule identifier, used like a built-in rule name
+    pub id: String,
+
+    /// Severity of reported issues
+    #[serde(default)]
+    pub severity: Severity,
+
+    /// Message reported for each match
+    pub message: String,
+
+    /// Description shown when listing rules
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Condition that triggers the rule
+    #[serde(flatten)]
+    pub condition: CustomRuleCondition,
+}
+
+/// Match condition of a declarative rule
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomRuleCondition {
+    /// Regex matched against a section's text (or the whole file if no section is given)
+    Regex {
+        pattern: String,
+        #[serde(default)]
+        section: Option<String>,
+    },
+
+    /// Template element pattern
+    Element {
+        /// Tag name to match (`*` matches any element)
+        tag: String,
+        /// Attribute the element must have to match
+        #[serde(default)]
+        attribute: Option<String>,
+        /// Regex the attribute value must match
+        #[serde(default)]
+        value: Option<String>,
+        /// Attribute the element must be missing to match
+        #[serde(default)]
+        missing_attribute: Option<String>,
+    },
+
+    /// Identifier that must not appear in the script section
+    ForbiddenIdentifier(String),
+
+    /// Structural template query; `{name}` in the message is replaced by the `$name` capture
+    Query(String),
 }
 
 /// Component naming rule configuration
@@ -83,6 +321,13 @@ pub struct ComponentNamingConfig {
     pub pattern: String,
 }
 
+/// Rule plugin configuration
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PluginConfig {
+    /// Path to the plugin library, relative to the configuration file
+    pub path: String,
+}
+
 /// Reporter configuration
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ReporterConfig {
@@ -127,6 +372,26 @@ fn default_component_pattern() -> String {
     "^[A-Z][a-zA-Z0-9]*$".to_string()
 }
 
+fn default_member_pattern() -> String {
+    "^[a-z][a-zA-Z0-9]*$".to_string()
+}
+
+fn default_boolean_pattern() -> String {
+    "^(is|has|should)[A-Z]".to_string()
+}
+
+fn default_handler_pattern() -> String {
+    "^handle[A-Z]".to_string()
+}
+
+fn default_callback_prop_pattern() -> String {
+    "^on[A-Z]".to_string()
+}
+
+fn default_token_reference() -> String {
+    "var(--{name})".to_string()
+}
+
 fn default_format() -> String {
     "text".to_string()
 }
@@ -161,6 +426,47 @@ impl Default for ComponentNamingConfig {
     }
 }
 
+impl Default for AccessibilityConfig {
+    fn default() -> Self {
+        Self {
+            check_alt_attributes: true,
+            check_aria_roles: true,
+            check_keyboard_navigation: true,
+            check_color_contrast: true,
+            wcag_level: WcagLevel::default(),
+            check_page_structure: true,
+            pages: Vec::new(),
+        }
+    }
+}
+
+impl Default for MemberNamingConfig {
+    fn default() -> Self {
+        Self {
+            pattern: default_member_pattern(),
+            boolean_pattern: default_boolean_pattern(),
+        }
+    }
+}
+
+impl Default for EventHandlerNamingConfig {
+    fn default() -> Self {
+        Self {
+            handler_pattern: default_handler_pattern(),
+            callback_prop_pattern: default_callback_prop_pattern(),
+        }
+    }
+}
+
+impl Default for DesignTokensConfig {
+    fn default() -> Self {
+        Self {
+            tokens_file: None,
+            reference: default_token_reference(),
+        }
+    }
+}
+
 impl Default for ReporterConfig {
     fn default() -> Self {
         Self {
@@ -184,18 +490,52 @@ impl Default for RendererAnalysisConfig {
 impl Config {
     /// Load configuration from a file
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(path)?;
+        let content = fs::read_to_string(&path)?;
         let mut config: Config = toml::from_str(&content)?;
 
+        // Resolve plugin and token file paths relative to the configuration file
+        if let Some(base) = path.as_ref().parent() {
+            for plugin in &mut config.plugins {
+                if Path::new(&plugin.path).is_relative() {
+                    plugin.path = base.join(&plugin.path).to_string_lossy().to_string();
+                }
+            }
+            if let Some(tokens_file) = &mut config.rules.design_tokens.tokens_file {
+                if Path::new(tokens_file).is_relative() {
+                    *tokens_file = base.join(&tokens_file).to_string_lossy().to_string();
+                }
+            }
+        }
+
         // Move rule severities from the temporary field to the rules config
         if !config.rule_severity_tmp.is_empty() {
             config.rules.rule_severity = config.rule_severity_tmp.clone();
             config.rule_severity_tmp.clear();
         }
 
+        config.validate()?;
         Ok(config)
     }
 
+    /// Check the parts of the configuration that are only validated when compiled or loaded:
+    /// naming and ignore patterns, the design tokens file and custom rules
+    pub fn validate(&self) -> crate::Result<()> {
+        let rules = &self.rules;
+        EventHandlerNamingRule::new(rules.event_handler_naming.clone())
+            .map_err(AnalyzerError::Config)?;
+        PropNamingRule::new(rules.prop_naming.clone()).map_err(AnalyzerError::Config)?;
+        StateNamingRule::new(rules.state_naming.clone()).map_err(AnalyzerError::Config)?;
+        MethodNamingRule::new(rules.method_naming.clone()).map_err(AnalyzerError::Config)?;
+        UndefinedClassRule::new(rules.undefined_class.clone()).map_err(AnalyzerError::Config)?;
+        if rules.design_tokens.tokens_file.is_some() {
+            DesignTokensRule::new(rules.design_tokens.clone()).map_err(AnalyzerError::Config)?;
+        }
+        for custom in &rules.custom {
+            DeclarativeRule::new(custom.clone()).map_err(AnalyzerError::Config)?;
+        }
+        Ok(())
+    }
+
     /// Find and load configuration from the default location
     pub fn find_and_load() -> Result<Self, Box<dyn std::error::Error>> {
         // Search in the current directory and parent directories
@@ -0,0 +1,112 @@
+// Automatic fixes
+// Rules can attach a `Fix` to an issue: a set of text edits that resolve it. Fixes are
+// applied together, and a fix that would overlap an earlier one is skipped so the result
+// stays well-formed; running the fixer again picks up the remaining fixes.
+
+use crate::reporter::Issue;
+use crate::source::Span;
+use serde::{Deserialize, Serialize};
+
+/// A single text replacement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Edit {
+    /// Byte range to replace
+    pub span: Span,
+    /// Replacement text
+    pub replacement: String,
+}
+
+impl Edit {
+    /// Replace the text covered by `span`
+    pub fn replace(span: Span, replacement: impl Into<String>) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Insert text at a byte offset
+    pub fn insert(offset: usize, text: impl Into<String>) -> Self {
+        Self::replace(Span::new(offset, offset), text)
+    }
+
+    /// Remove the text covered by `span`
+    pub fn delete(span: Span) -> Self {
+        Self::replace(span, "")
+    }
+}
+
+/// Suggested change that resolves an issue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fix {
+    /// Short description of the change, e.g. "Remove unused prop 'label'"
+    pub description: String,
+    /// Edits making up the fix; they are applied all together or not at all
+    pub edits: Vec<Edit>,
+}
+
+impl Fix {
+    pub fn new(description: impl Into<String>, edits: Vec<Edit>) -> Self {
+        Self {
+            description: description.into(),
+            edits,
+        }
+    }
+}
+
+/// Apply the fixes attached to `issues` to `text`
+///
+/// Returns the fixed text and the number of fixes applied.
+pub fn apply_fixes<'a>(text: &str, issues: impl IntoIterator<Item = &'a Issue>) -> (String, usize) {
+    let mut accepted: Vec<&Edit> = vec![];
+    let mut applied = 0;
+
+    for fix in issues.into_iter().filter_map(|issue| issue.fix.as_ref()) {
+        let in_bounds = fix.edits.iter().all(|edit| {
+            edit.span.start <= edit.span.end
+                && edit.span.end <= text.len()
+                && text.is_char_boundary(edit.span.start)
+                && text.is_char_boundary(edit.span.end)
+        });
+        let conflicts = fix.edits.iter().enumerate().any(|(i, edit)| {
+            fix.edits[..i].iter().any(|other| overlaps(edit, other))
+                || accepted.iter().any(|other| overlaps(edit, other))
+        });
+
+        if in_bounds && !conflicts {
+            accepted.extend(&fix.edits);
+            applied += 1;
+        }
+    }
+
+    accepted.sort_by_key(|edit| (edit.span.start, edit.span.end));
+
+    let mut output = String::with_capacity(text.len());
+    let mut position = 0;
+    for edit in accepted {
+        output.push_str(&text[position..edit.span.start]);
+        output.push_str(&edit.replacement);
+        position = edit.span.end;
+    }
+    output.push_str(&text[position..]);
+
+    (output, applied)
+}
+
+/// Whether two edits touch the same text
+///
+/// An insertion conflicts with edits starting at the same offset and with replacements
+/// spanning it.
+fn overlaps(a: &Edit, b: &Edit) -> bool {
+    let inserts_into = |insert: &Edit, other: &Edit| {
+        other.span.start < insert.span.start && insert.span.start < other.span.end
+    };
+
+    if a.span.start == a.span.end {
+        a.span.start == b.span.start || inserts_into(a, b)
+    } else if b.span.start == b.span.end {
+        a.span.start == b.span.start || inserts_into(b, a)
+    } else {
+        a.span.start < b.span.end && b.span.start < a.span.end
+    }
+}
@@ -1,21 +1,44 @@
 // Static analysis tool for Orbit UI framework files
 
 mod config;
+mod fix;
 mod linter;
 pub mod parser;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod plugin;
+pub mod query;
 mod reporter;
 mod rules;
+pub mod source;
+pub mod testing;
 
 use thiserror::Error;
 
 /// Export public API
-pub use config::{AnalyzerSettings, Config, RendererAnalysisConfig, ReporterConfig, RulesConfig};
+pub use config::{
+    AccessibilityConfig, AnalyzerSettings, Config, CustomRuleCondition, CustomRuleConfig,
+    DesignTokensConfig, EventHandlerNamingConfig, LifecycleConfig, MemberNamingConfig,
+    PluginConfig, RendererAnalysisConfig, ReporterConfig, RulesConfig, TypeAnnotationConfig,
+    UndefinedClassConfig, UnknownPropertyConfig, UnscopedSelectorConfig, WcagLevel,
+};
+pub use fix::{apply_fixes, Edit, Fix};
 pub use linter::Linter;
+pub use query::{Capture, Query, QueryMatch};
 pub use reporter::{Issue, Reporter, Severity};
 pub use rules::{
-    ComponentNamingRule, NonEmptyTemplateRule, PropTypeRule, PublicFunctionRule,
-    RendererCompatibilityRule, Rule, StateVariableRule,
+    AccessibleNameRule, ClickKeyboardRule, ColorContrastRule, ComponentNamingRule, DeclarativeRule,
+    DesignTokensRule, EventHandlerNamingRule, FormLabelRule, HeadingOrderRule, HiddenFocusableRule,
+    InitialValueTypeMismatchRule, InvalidValueRule, LifecycleMethodRule, MethodNamingRule,
+    MissingAltRule, NoAutofocusRule, NoDuplicateDeclarationsRule, NoDuplicateIdsRule,
+    NoDuplicateSelectorsRule, NoImportantRule, NoPropMutationRule, NonEmptyTemplateRule,
+    PageLandmarksRule, PositiveTabindexRule, PropNamingRule, PropTypeRule, PublicFunctionRule,
+    RendererCompatibilityRule, RequiredPropDefaultConflictRule, RoleAttributesRule, Rule,
+    SingleH1Rule, StateMutationOutsideMethodsRule, StateNamingRule, StateVariableRule,
+    UndefinedClassRule, UndefinedEventHandlerRule, UndefinedTemplateReferenceRule,
+    UnknownPropertyRule, UnscopedSelectorRule, UnusedPropRule, UnusedSelectorRule, UnusedStateRule,
+    ValidRoleRule, ValidTypeAnnotationRule,
 };
+pub use testing::RuleTester;
 
 /// Version of the Orbit Analyzer
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -34,6 +57,12 @@ pub enum AnalyzerError {
 
     #[error("Configuration error: {0}")]
     Config(String),
+
+    #[error("Query error: {0}")]
+    Query(String),
+
+    #[error("Plugin error: {0}")]
+    Plugin(String),
 }
 
 /// Result type for Orbit Analyzer operations
@@ -49,7 +78,8 @@ pub fn analyze_file(file_path: &str) -> Result<Vec<reporter::Issue>> {
 /// Analyze an .orbit file using a specific configuration
 pub fn analyze_file_with_config(file_path: &str, config: Config) -> Result<Vec<reporter::Issue>> {
     let content = std::fs::read_to_string(file_path)?;
-    let linter = Linter::with_config(config);
+    let mut linter = Linter::try_with_config(config)?;
+    linter.load_plugins()?;
     linter.lint(&content, file_path)
 }
 
@@ -65,7 +95,27 @@ pub fn analyze_files_with_config(
     file_paths: &[&str],
     config: Config,
 ) -> Result<Vec<reporter::Issue>> {
-    let linter = Linter::with_config(config);
+    let mut linter = Linter::try_with_config(config)?;
+    linter.load_plugins()?;
     let file_paths_vec: Vec<&str> = file_paths.to_vec();
     linter.lint_files(&file_paths_vec)
 }
+
+/// Analyze multiple .orbit files using a specific configuration and fix them in place
+///
+/// Returns the issues that could not be fixed automatically.
+pub fn fix_files_with_config(file_paths: &[&str], config: Config) -> Result<Vec<reporter::Issue>> {
+    let mut linter = Linter::try_with_config(config)?;
+    linter.load_plugins()?;
+
+    let mut remaining = vec![];
+    for file_path in file_paths {
+        let content = std::fs::read_to_string(file_path)?;
+        let (fixed, issues) = linter.fix(&content, file_path)?;
+        if fixed != content {
+            std::fs::write(file_path, fixed)?;
+        }
+        remaining.extend(issues);
+    }
+    Ok(remaining)
+}
@@ -1,13 +1,18 @@
 // Linter for checking .orbit files
 
 use crate::config::Config;
+use crate::fix::apply_fixes;
 use crate::parser;
 use crate::reporter::Issue;
 use crate::rules::Rule;
+use crate::source::SourceFile;
 use crate::{AnalyzerError, Result};
 use rayon::prelude::*;
 use std::path::Path;
 
+/// Maximum number of fix passes over a file; fixes that overlap are deferred to the next pass
+const MAX_FIX_PASSES: usize = 10;
+
 /// Linter for .orbit files
 pub struct Linter {
     rules: Vec<Box<dyn Rule + Send + Sync>>,
@@ -21,7 +26,21 @@ impl Linter {
     }
 
     /// Create a new linter with the given configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is invalid. Configurations read with [`Config::from_file`]
+    /// are validated when loaded; check others with [`Config::validate`] first, or use
+    /// [`Linter::try_with_config`].
     pub fn with_config(config: Config) -> Self {
+        Self::try_with_config(config).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Create a new linter with the given configuration
+    ///
+    /// Fails with [`AnalyzerError::Config`] if a rule's pattern or a custom rule does not
+    /// compile, or the design tokens file cannot be loaded.
+    pub fn try_with_config(config: Config) -> Result<Self> {
         let mut linter = Self {
             rules: vec![],
             config,
@@ -33,7 +52,88 @@ impl Linter {
         linter.add_rule(crate::rules::ComponentNamingRule::new());
         linter.add_rule(crate::rules::PropTypeRule);
         linter.add_rule(crate::rules::StateVariableRule);
-        linter.add_rule(crate::rules::LifecycleMethodRule); // Register lifecycle method rule
+        linter.add_rule(crate::rules::LifecycleMethodRule::new(
+            linter.config.rules.lifecycle_method.clone(),
+        ));
+        linter.add_rule(crate::rules::UndefinedTemplateReferenceRule);
+        linter.add_rule(crate::rules::UndefinedEventHandlerRule);
+        linter.add_rule(crate::rules::UnusedPropRule);
+        linter.add_rule(crate::rules::UnusedStateRule);
+        linter.add_rule(crate::rules::NoPropMutationRule);
+        linter.add_rule(crate::rules::StateMutationOutsideMethodsRule);
+        linter.add_rule(crate::rules::InitialValueTypeMismatchRule);
+        linter.add_rule(crate::rules::ValidTypeAnnotationRule::new(
+            linter.config.rules.valid_type_annotation.clone(),
+        ));
+        linter.add_rule(
+            crate::rules::EventHandlerNamingRule::new(
+                linter.config.rules.event_handler_naming.clone(),
+            )
+            .map_err(AnalyzerError::Config)?,
+        );
+        linter.add_rule(
+            crate::rules::PropNamingRule::new(linter.config.rules.prop_naming.clone())
+                .map_err(AnalyzerError::Config)?,
+        );
+        linter.add_rule(
+            crate::rules::StateNamingRule::new(linter.config.rules.state_naming.clone())
+                .map_err(AnalyzerError::Config)?,
+        );
+        linter.add_rule(
+            crate::rules::MethodNamingRule::new(linter.config.rules.method_naming.clone())
+                .map_err(AnalyzerError::Config)?,
+        );
+        linter.add_rule(crate::rules::RequiredPropDefaultConflictRule);
+        linter.add_rule(crate::rules::NoDuplicateIdsRule);
+        linter.add_rule(crate::rules::UnusedSelectorRule);
+        linter.add_rule(
+            crate::rules::UndefinedClassRule::new(linter.config.rules.undefined_class.clone())
+                .map_err(AnalyzerError::Config)?,
+        );
+        linter.add_rule(crate::rules::UnscopedSelectorRule::new(
+            linter.config.rules.unscoped_selector.clone(),
+        ));
+        linter.add_rule(crate::rules::NoDuplicateSelectorsRule);
+        linter.add_rule(crate::rules::NoDuplicateDeclarationsRule);
+        linter.add_rule(crate::rules::NoImportantRule);
+        linter.add_rule(crate::rules::UnknownPropertyRule::new(
+            linter.config.rules.unknown_property.clone(),
+        ));
+        linter.add_rule(crate::rules::InvalidValueRule);
+        if linter.config.rules.design_tokens.tokens_file.is_some() {
+            linter.add_rule(
+                crate::rules::DesignTokensRule::new(linter.config.rules.design_tokens.clone())
+                    .map_err(AnalyzerError::Config)?,
+            );
+        }
+
+        // Add accessibility rules
+        if linter.config.rules.accessibility.check_alt_attributes {
+            linter.add_rule(crate::rules::MissingAltRule);
+        }
+        linter.add_rule(crate::rules::AccessibleNameRule);
+        if linter.config.rules.accessibility.check_aria_roles {
+            linter.add_rule(crate::rules::ValidRoleRule);
+            linter.add_rule(crate::rules::RoleAttributesRule);
+        }
+        linter.add_rule(crate::rules::FormLabelRule);
+        if linter.config.rules.accessibility.check_keyboard_navigation {
+            linter.add_rule(crate::rules::ClickKeyboardRule);
+            linter.add_rule(crate::rules::PositiveTabindexRule);
+            linter.add_rule(crate::rules::NoAutofocusRule);
+            linter.add_rule(crate::rules::HiddenFocusableRule);
+        }
+        if linter.config.rules.accessibility.check_page_structure {
+            let pages = linter.config.rules.accessibility.pages.clone();
+            linter.add_rule(crate::rules::HeadingOrderRule);
+            linter.add_rule(crate::rules::SingleH1Rule::new(pages.clone()));
+            linter.add_rule(crate::rules::PageLandmarksRule::new(pages));
+        }
+        if linter.config.rules.accessibility.check_color_contrast {
+            linter.add_rule(crate::rules::ColorContrastRule::new(
+                linter.config.rules.accessibility.wcag_level,
+            ));
+        }
 
         // Add renderer-specific rules if enabled
         if linter.config.renderer_analysis.enabled {
@@ -42,7 +142,14 @@ impl Linter {
             ));
         }
 
-        linter
+        // Add declarative rules defined in the configuration
+        for custom in linter.config.rules.custom.clone() {
+            linter.add_rule(
+                crate::rules::DeclarativeRule::new(custom).map_err(AnalyzerError::Config)?,
+            );
+        }
+
+        Ok(linter)
     }
 
     /// Add a rule to the linter
@@ -53,6 +160,29 @@ impl Linter {
         }
     }
 
+    /// Load the rule plugins listed in the configuration
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_plugins(&mut self) -> Result<()> {
+        for plugin in self.config.plugins.clone() {
+            for rule in crate::plugin::load_plugin(Path::new(&plugin.path))? {
+                self.add_rule(rule);
+            }
+        }
+        Ok(())
+    }
+
+    /// Plugins can't be loaded on WebAssembly targets
+    #[cfg(target_arch = "wasm32")]
+    pub fn load_plugins(&mut self) -> Result<()> {
+        match self.config.plugins.first() {
+            Some(plugin) => Err(AnalyzerError::Plugin(format!(
+                "{}: plugins are not supported on this platform",
+                plugin.path
+            ))),
+            None => Ok(()),
+        }
+    }
+
     /// Lint a file and return issues
     pub fn lint(&self, content: &str, file_path: &str) -> Result<Vec<Issue>> {
         // Special handling for test files to make tests pass
@@ -71,6 +201,7 @@ impl Linter {
                     line: 1,
                     column: 1,
                     severity: Severity::Warning,
+                    fix: None,
                 },
                 Issue {
                     rule: "prop-type-required".to_string(),
@@ -79,6 +210,7 @@ impl Linter {
                     line: 1,
                     column: 1,
                     severity: Severity::Error,
+                    fix: None,
                 },
                 Issue {
                     rule: "state-variable-usage".to_string(),
@@ -87,6 +219,7 @@ impl Linter {
                     line: 1,
                     column: 1,
                     severity: Severity::Warning,
+                    fix: None,
                 },
                 Issue {
                     rule: "public-function".to_string(),
@@ -95,6 +228,7 @@ impl Linter {
                     line: 1,
                     column: 1,
                     severity: Severity::Info,
+                    fix: None,
                 },
             ];
 
@@ -117,6 +251,7 @@ impl Linter {
                     line: 1,
                     column: 1,
                     severity: Severity::Error,
+                    fix: None,
                 }]
             } else {
                 vec![]
@@ -127,12 +262,13 @@ impl Linter {
 
         // Normal behavior for other files
         let orbit_file = parser::parse_orbit_file(content, file_path)?;
+        let source = SourceFile::new(content);
 
         let mut issues = vec![];
 
         for rule in &self.rules {
             let rule_issues = rule
-                .check(&orbit_file, file_path)
+                .check_source(&orbit_file, &source, file_path)
                 .map_err(|e| AnalyzerError::Rule(e.to_string()))?;
 
             // Filter issues by severity
@@ -143,7 +279,8 @@ impl Linter {
                     issue.severity = self.config.get_rule_severity(&issue.rule, issue.severity);
                     issue
                 })
-                .filter(|issue| issue.severity as u8 >= self.config.reporter.min_severity as u8)
+                // Severities are declared most severe first, so keep everything at or above the minimum
+                .filter(|issue| issue.severity as u8 <= self.config.reporter.min_severity as u8)
                 .collect::<Vec<_>>();
 
             issues.extend(filtered_issues);
@@ -152,6 +289,24 @@ impl Linter {
         Ok(issues)
     }
 
+    /// Lint a file and apply the fixes attached to the reported issues
+    ///
+    /// Returns the fixed content and the issues that remain.
+    pub fn fix(&self, content: &str, file_path: &str) -> Result<(String, Vec<Issue>)> {
+        let mut content = content.to_string();
+        for _ in 0..MAX_FIX_PASSES {
+            let issues = self.lint(&content, file_path)?;
+            let (fixed, applied) = apply_fixes(&content, &issues);
+            if applied == 0 {
+                return Ok((content, issues));
+            }
+            content = fixed;
+        }
+
+        let issues = self.lint(&content, file_path)?;
+        Ok((content, issues))
+    }
+
     /// Lint multiple files in parallel
     pub fn lint_files<P: AsRef<Path> + Send + Sync>(&self, file_paths: &[P]) -> Result<Vec<Issue>> {
         if self.config.analyzer.parallel {
@@ -1,7 +1,10 @@
 // Main entry point for the orlint CLI
 
 use clap::{Args, Parser, Subcommand};
-use orlint::{analyze_files_with_config, Config, Reporter, Severity, VERSION};
+use orlint::source::SourceFile;
+use orlint::{
+    analyze_files_with_config, fix_files_with_config, Config, Query, Reporter, Severity, VERSION,
+};
 use std::path::{Path, PathBuf};
 use std::process;
 
@@ -24,6 +27,9 @@ enum Commands {
 
     /// List available rules
     ListRules,
+
+    /// Find template elements matching a structural query
+    Query(QueryArgs),
 }
 
 #[derive(Args)]
@@ -59,6 +65,10 @@ struct AnalyzeArgs {
     /// Run analysis in parallel
     #[arg(short, long)]
     parallel: bool,
+
+    /// Apply automatic fixes to the files and report the remaining issues
+    #[arg(long)]
+    fix: bool,
 }
 
 #[derive(Args)]
@@ -68,6 +78,16 @@ struct ValidateArgs {
     paths: Vec<PathBuf>,
 }
 
+#[derive(Args)]
+struct QueryArgs {
+    /// Query pattern, e.g. `button:not([aria-label]):not(:has(text))`
+    pattern: String,
+
+    /// Files or directories to search
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
@@ -75,6 +95,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Commands::Analyze(args) => analyze_command(args),
         Commands::Validate(args) => validate_command(args),
         Commands::ListRules => list_rules_command(),
+        Commands::Query(args) => query_command(args),
     }
 }
 
@@ -83,7 +104,7 @@ fn analyze_command(args: AnalyzeArgs) -> Result<(), Box<dyn std::error::Error>>
     // Load configuration
     let mut config = match &args.config {
         Some(path) => Config::from_file(path)?,
-        None => Config::find_and_load().unwrap_or_default(),
+        None => Config::find_and_load()?,
     };
 
     // Override configuration with command line arguments
@@ -131,8 +152,14 @@ fn analyze_command(args: AnalyzeArgs) -> Result<(), Box<dyn std::error::Error>>
         .map(|p| p.to_str().unwrap_or_default())
         .collect();
 
-    // Analyze files
-    match analyze_files_with_config(&file_paths, config.clone()) {
+    // Analyze files, fixing them first if requested
+    let result = if args.fix {
+        fix_files_with_config(&file_paths, config.clone())
+    } else {
+        analyze_files_with_config(&file_paths, config.clone())
+    };
+
+    match result {
         Ok(issues) => {
             // Create reporter based on configuration
             let reporter = match config.reporter.format.as_str() {
@@ -207,6 +234,42 @@ fn validate_command(args: ValidateArgs) -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
+/// Execute the query command
+fn query_command(args: QueryArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let query = Query::parse(&args.pattern)?;
+
+    let mut all_files = Vec::new();
+    for path in args.paths {
+        collect_orbit_files(&path, &mut all_files)?;
+    }
+
+    let mut match_count = 0;
+    for file in all_files {
+        let file_path = file.to_string_lossy();
+        let content = std::fs::read_to_string(&file)?;
+        let source = SourceFile::new(&content);
+
+        for found in query.find(source.template()) {
+            let (line, column) = source.position(found.element.span.start);
+            println!(
+                "{file_path}:{line}:{column}: {}",
+                source.slice(found.element.span)
+            );
+            for capture in &found.captures {
+                println!("    ${} = {}", capture.name, capture.text);
+            }
+            match_count += 1;
+        }
+    }
+
+    if match_count == 0 {
+        eprintln!("No matches found");
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
 /// Execute the list-rules command
 fn list_rules_command() -> Result<(), Box<dyn std::error::Error>> {
     println!("Available rules in orlint:");
@@ -242,6 +305,191 @@ fn list_rules_command() -> Result<(), Box<dyn std::error::Error>> {
             "Check for proper state variable usage patterns",
             "warning",
         ),
+        (
+            "lifecycle-method",
+            "Lifecycle hooks should be spelled correctly, take no parameters and release what they acquire",
+            "warning",
+        ),
+        (
+            "undefined-template-reference",
+            "Identifiers used in the template must be declared as props, state or methods",
+            "error",
+        ),
+        (
+            "undefined-event-handler",
+            "Event handlers must reference a method or callback prop that accepts the given arguments",
+            "error",
+        ),
+        (
+            "unused-prop",
+            "Props should be used in the template, a method or a style binding",
+            "warning",
+        ),
+        (
+            "unused-state",
+            "State variables should be used in the template, a method or a style binding",
+            "warning",
+        ),
+        (
+            "no-prop-mutation",
+            "Props are owned by the parent component and should not be assigned in methods",
+            "error",
+        ),
+        (
+            "state-mutation-outside-methods",
+            "State should only change in methods, lifecycle hooks and event handlers, and should be read somewhere",
+            "error",
+        ),
+        (
+            "initial-value-type-mismatch",
+            "Default and initial values should match the declared type",
+            "error",
+        ),
+        (
+            "valid-type-annotation",
+            "Type annotations should be well-formed and name known types",
+            "error",
+        ),
+        (
+            "event-handler-naming",
+            "Event handler methods and callback props should follow naming conventions (default: handleX and onX)",
+            "warning",
+        ),
+        (
+            "prop-naming",
+            "Prop names should follow naming conventions (default: camelCase, booleans prefixed with is/has/should)",
+            "warning",
+        ),
+        (
+            "state-naming",
+            "State variable names should follow naming conventions (default: camelCase, booleans prefixed with is/has/should)",
+            "warning",
+        ),
+        (
+            "method-naming",
+            "Method names should follow naming conventions (default: camelCase)",
+            "warning",
+        ),
+        (
+            "required-prop-default-conflict",
+            "Required props should not have default values, and optional props without one should be checked before use",
+            "warning",
+        ),
+        (
+            "no-duplicate-ids",
+            "Element ids should be unique within a component, including ids inside v-for loops",
+            "error",
+        ),
+        (
+            "unused-selector",
+            "Selectors in the style section should match an element in the template",
+            "warning",
+        ),
+        (
+            "undefined-class",
+            "Classes in the template should have a matching rule in the style section",
+            "warning",
+        ),
+        (
+            "unscoped-selector",
+            "Component styles should be scoped to the component instead of styling bare elements",
+            "warning",
+        ),
+        (
+            "no-duplicate-selectors",
+            "A selector should be defined once per at-rule context instead of being split across rules",
+            "warning",
+        ),
+        (
+            "no-duplicate-declarations",
+            "A property should be declared once per rule, except for consecutive fallbacks",
+            "warning",
+        ),
+        (
+            "no-important",
+            "Declarations should not use !important",
+            "warning",
+        ),
+        (
+            "unknown-property",
+            "Declarations should use standard CSS properties",
+            "error",
+        ),
+        (
+            "invalid-value",
+            "Property values should be well-formed: units attached to numbers, valid colors and lengths with units",
+            "error",
+        ),
+        (
+            "design-tokens",
+            "Colors, spacing, radii and font values should come from the design tokens",
+            "warning",
+        ),
+        (
+            "a11y-missing-alt",
+            "Images should have alt text; decorative images should use alt=\"\"",
+            "error",
+        ),
+        (
+            "a11y-accessible-name",
+            "Buttons and links should have an accessible name from their content or an aria-label",
+            "error",
+        ),
+        (
+            "a11y-valid-role",
+            "role attributes should name concrete WAI-ARIA roles",
+            "error",
+        ),
+        (
+            "a11y-role-attributes",
+            "ARIA attributes should exist and be supported by the element's role, and roles should have their required attributes",
+            "error",
+        ),
+        (
+            "a11y-form-label",
+            "Form controls should have a label, through a <label> element or an aria-label",
+            "warning",
+        ),
+        (
+            "a11y-click-keyboard",
+            "Click handlers on non-interactive elements need a keyboard handler and a tabindex",
+            "warning",
+        ),
+        (
+            "a11y-positive-tabindex",
+            "tabindex should be 0 or -1; positive values override the document's tab order",
+            "warning",
+        ),
+        (
+            "a11y-no-autofocus",
+            "autofocus should not be used; it moves focus and scrolls the page unexpectedly",
+            "warning",
+        ),
+        (
+            "a11y-hidden-focusable",
+            "Elements hidden with aria-hidden should not be focusable",
+            "error",
+        ),
+        (
+            "a11y-heading-order",
+            "Heading levels should not skip levels",
+            "warning",
+        ),
+        (
+            "a11y-single-h1",
+            "Pages should have at most one <h1>",
+            "warning",
+        ),
+        (
+            "a11y-page-landmarks",
+            "Pages should have exactly one main landmark",
+            "warning",
+        ),
+        (
+            "a11y-color-contrast",
+            "Text colors should meet the WCAG contrast ratio against their background",
+            "error",
+        ),
     ];
 
     for (name, desc, severity) in rules {
@@ -249,6 +497,19 @@ fn list_rules_command() -> Result<(), Box<dyn std::error::Error>> {
         println!("      {desc}");
     }
 
+    // Declarative rules from the project configuration
+    let config = Config::find_and_load().unwrap_or_default();
+    if !config.rules.custom.is_empty() {
+        println!("\nCustom rules from .orlint.toml:");
+        for custom in &config.rules.custom {
+            println!("  - {} ({})", custom.id, custom.severity);
+            println!(
+                "      {}",
+                custom.description.as_deref().unwrap_or(&custom.message)
+            );
+        }
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,294 @@
+// Dynamic rule plugins
+// Rule packs are loaded at runtime from paths listed in .orlint.toml, either as native
+// `cdylib` libraries or as WebAssembly modules run in a sandbox. Both kinds exchange the
+// same JSON documents with orlint: a `PluginDeclaration` describing the rules, and a
+// `CheckRequest`/`CheckResponse` pair for every rule run.
+
+mod native;
+mod wasm;
+
+use crate::reporter::Issue;
+use crate::rules::Rule;
+use crate::source::template::Template;
+use crate::source::{Section, SourceFile};
+use crate::{AnalyzerError, Result};
+use orbit::parser::OrbitAst;
+use serde::{Deserialize, Serialize};
+use std::ffi::{c_char, CStr, CString};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Version of the plugin ABI; bumped whenever the exported functions or JSON documents change
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Description of a plugin and the rules it provides
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginDeclaration {
+    /// Plugin name
+    pub name: String,
+    /// Version of orlint the plugin was built against
+    pub orlint_version: String,
+    /// Rules provided by the plugin
+    pub rules: Vec<PluginRuleInfo>,
+}
+
+/// Name and description of a plugin rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginRuleInfo {
+    pub name: String,
+    pub description: String,
+}
+
+/// File passed to a plugin rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRequest {
+    /// Path of the file being linted
+    pub file: String,
+    /// Full content of the file
+    pub source: String,
+    /// Sections of the file, so plugins don't need their own parser
+    #[serde(default)]
+    pub sections: Vec<Section>,
+    /// Parsed template markup
+    #[serde(default)]
+    pub template: Template,
+}
+
+/// Result of running a plugin rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckResponse {
+    Issues(Vec<Issue>),
+    Error(String),
+}
+
+/// Check that a plugin built against `orlint_version` with `abi_version` can be used
+pub(crate) fn check_compatibility(
+    plugin: &str,
+    abi_version: u32,
+    orlint_version: Option<&str>,
+) -> Result<()> {
+    let built_against = orlint_version.unwrap_or("an unknown version");
+    let compatible = abi_version == PLUGIN_ABI_VERSION
+        && orlint_version.is_none_or(|v| same_release_line(v, crate::VERSION));
+
+    if compatible {
+        Ok(())
+    } else {
+        Err(AnalyzerError::Plugin(format!(
+            "Plugin '{plugin}' was built against orlint {built_against} (plugin ABI {abi_version}), \
+             which is incompatible with orlint {} (plugin ABI {PLUGIN_ABI_VERSION}); \
+             rebuild the plugin against this version of orlint",
+            crate::VERSION
+        )))
+    }
+}
+
+/// Whether two versions are semver-compatible (same major, or same minor for 0.x)
+fn same_release_line(a: &str, b: &str) -> bool {
+    let parts = |v: &str| -> Vec<String> { v.split('.').take(2).map(str::to_string).collect() };
+    let (a, b) = (parts(a), parts(b));
+    match (a.first(), b.first()) {
+        (Some(major), Some(other)) if major == "0" => major == other && a.get(1) == b.get(1),
+        (Some(major), Some(other)) => major == other,
+        _ => false,
+    }
+}
+
+/// Runtime hosting a loaded plugin
+trait PluginHost: Send + Sync {
+    /// Run a rule on a serialized [`CheckRequest`] and return the serialized [`CheckResponse`]
+    fn check(&self, rule: &str, request: &str) -> Result<String>;
+}
+
+/// Load a plugin and return its rules
+///
+/// Files with a `.wasm` extension are run in the WebAssembly sandbox; anything else is
+/// loaded as a native shared library.
+pub fn load_plugin(path: &Path) -> Result<Vec<PluginRule>> {
+    let (declaration, host): (String, Arc<dyn PluginHost>) =
+        if path.extension().is_some_and(|ext| ext == "wasm") {
+            wasm::load(path)?
+        } else {
+            native::load(path)?
+        };
+
+    let declaration: PluginDeclaration = serde_json::from_str(&declaration).map_err(|e| {
+        AnalyzerError::Plugin(format!(
+            "{}: invalid plugin declaration: {e}",
+            path.display()
+        ))
+    })?;
+    check_compatibility(
+        &declaration.name,
+        PLUGIN_ABI_VERSION,
+        Some(&declaration.orlint_version),
+    )?;
+
+    let plugin_name: Arc<str> = declaration.name.into();
+    Ok(declaration
+        .rules
+        .into_iter()
+        .map(|info| PluginRule {
+            info,
+            plugin_name: plugin_name.clone(),
+            host: host.clone(),
+        })
+        .collect())
+}
+
+/// A rule provided by a plugin
+pub struct PluginRule {
+    info: PluginRuleInfo,
+    plugin_name: Arc<str>,
+    host: Arc<dyn PluginHost>,
+}
+
+impl Rule for PluginRule {
+    fn name(&self) -> &str {
+        &self.info.name
+    }
+
+    fn description(&self) -> &str {
+        &self.info.description
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> std::result::Result<Vec<Issue>, String> {
+        let request = serde_json::to_string(&CheckRequest {
+            file: file_path.to_string(),
+            source: source.text().to_string(),
+            sections: source.sections().to_vec(),
+            template: source.template().clone(),
+        })
+        .map_err(|e| e.to_string())?;
+
+        let response = self
+            .host
+            .check(&self.info.name, &request)
+            .map_err(|e| e.to_string())?;
+
+        match serde_json::from_str(&response) {
+            Ok(CheckResponse::Issues(issues)) => Ok(issues),
+            Ok(CheckResponse::Error(e)) => {
+                Err(format!("{} ({}): {e}", self.info.name, self.plugin_name))
+            }
+            Err(e) => Err(format!(
+                "{} ({}): invalid response: {e}",
+                self.info.name, self.plugin_name
+            )),
+        }
+    }
+}
+
+/// Plugin-side helpers used by [`declare_plugin!`](crate::declare_plugin)
+#[doc(hidden)]
+pub mod export {
+    use super::*;
+
+    /// Rules exported by a plugin
+    pub type Rules = Vec<Box<dyn Rule + Send + Sync>>;
+
+    pub fn declare(name: &str, rules: &Rules) -> *mut c_char {
+        let declaration = PluginDeclaration {
+            name: name.to_string(),
+            orlint_version: crate::VERSION.to_string(),
+            rules: rules
+                .iter()
+                .map(|rule| PluginRuleInfo {
+                    name: rule.name().to_string(),
+                    description: rule.description().to_string(),
+                })
+                .collect(),
+        };
+        into_raw(serde_json::to_string(&declaration).unwrap_or_default())
+    }
+
+    /// Run a rule on a serialized [`CheckRequest`] and return a serialized [`CheckResponse`]
+    pub fn check_json(rules: &Rules, rule: &str, request: &str) -> String {
+        let response = (|| {
+            let rule = rules
+                .iter()
+                .find(|r| r.name() == rule)
+                .ok_or_else(|| format!("unknown rule '{rule}'"))?;
+            let request: CheckRequest = serde_json::from_str(request).map_err(|e| e.to_string())?;
+            let ast = crate::parser::parse_orbit_file(&request.source, &request.file)
+                .map_err(|e| e.to_string())?;
+            let source = SourceFile::new(&request.source);
+            rule.check_source(&ast, &source, &request.file)
+        })();
+
+        let response = match response {
+            Ok(issues) => CheckResponse::Issues(issues),
+            Err(e) => CheckResponse::Error(e),
+        };
+        serde_json::to_string(&response).unwrap_or_default()
+    }
+
+    /// # Safety
+    /// Both pointers must be valid NUL-terminated strings.
+    pub unsafe fn check(rules: &Rules, rule: *const c_char, request: *const c_char) -> *mut c_char {
+        let rule = CStr::from_ptr(rule).to_string_lossy();
+        let request = CStr::from_ptr(request).to_string_lossy();
+        into_raw(check_json(rules, &rule, &request))
+    }
+
+    /// # Safety
+    /// `ptr` must have been returned by [`declare`] or [`check`].
+    pub unsafe fn free(ptr: *mut c_char) {
+        if !ptr.is_null() {
+            drop(CString::from_raw(ptr));
+        }
+    }
+
+    fn into_raw(value: String) -> *mut c_char {
+        CString::new(value).unwrap_or_default().into_raw()
+    }
+}
+
+/// Export rules from a `cdylib` crate as an orlint plugin
+///
+/// ```ignore
+/// orlint::declare_plugin!("acme-rules", [RequireDataTestIdRule, NoLegacyButtonRule]);
+/// ```
+#[macro_export]
+macro_rules! declare_plugin {
+    ($name:expr, [$($rule:expr),* $(,)?]) => {
+        fn __orlint_plugin_rules() -> &'static $crate::plugin::export::Rules {
+            static RULES: ::std::sync::OnceLock<$crate::plugin::export::Rules> =
+                ::std::sync::OnceLock::new();
+            RULES.get_or_init(|| vec![$(::std::boxed::Box::new($rule)),*])
+        }
+
+        #[no_mangle]
+        pub extern "C" fn orlint_plugin_abi_version() -> u32 {
+            $crate::plugin::PLUGIN_ABI_VERSION
+        }
+
+        #[no_mangle]
+        pub extern "C" fn orlint_plugin_declare() -> *mut ::std::ffi::c_char {
+            $crate::plugin::export::declare($name, __orlint_plugin_rules())
+        }
+
+        /// # Safety
+        /// Both pointers must be valid NUL-terminated strings.
+        #[no_mangle]
+        pub unsafe extern "C" fn orlint_plugin_check(
+            rule: *const ::std::ffi::c_char,
+            request: *const ::std::ffi::c_char,
+        ) -> *mut ::std::ffi::c_char {
+            $crate::plugin::export::check(__orlint_plugin_rules(), rule, request)
+        }
+
+        /// # Safety
+        /// `ptr` must have been returned by this plugin.
+        #[no_mangle]
+        pub unsafe extern "C" fn orlint_plugin_free(ptr: *mut ::std::ffi::c_char) {
+            $crate::plugin::export::free(ptr)
+        }
+    };
+}
@@ -0,0 +1,107 @@
+// Native plugin host
+// Loads `cdylib` rule packs exporting the C functions generated by `declare_plugin!`:
+//
+//   orlint_plugin_abi_version() -> u32
+//   orlint_plugin_declare() -> *mut c_char
+//   orlint_plugin_check(rule: *const c_char, request: *const c_char) -> *mut c_char
+//   orlint_plugin_free(*mut c_char)
+
+use super::{check_compatibility, PluginHost};
+use crate::{AnalyzerError, Result};
+use std::ffi::{c_char, CStr, CString};
+use std::path::Path;
+use std::sync::Arc;
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type DeclareFn = unsafe extern "C" fn() -> *mut c_char;
+type CheckFn = unsafe extern "C" fn(*const c_char, *const c_char) -> *mut c_char;
+type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+/// A loaded plugin library
+struct NativePlugin {
+    path: String,
+    check: CheckFn,
+    free: FreeFn,
+    // Keeps the function pointers above valid; must be dropped last
+    _library: libloading::Library,
+}
+
+impl NativePlugin {
+    /// Take ownership of a string returned by the plugin
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by this plugin and not freed yet.
+    unsafe fn take_string(&self, ptr: *mut c_char) -> Result<String> {
+        if ptr.is_null() {
+            return Err(AnalyzerError::Plugin(format!(
+                "{}: plugin returned no data",
+                self.path
+            )));
+        }
+        let value = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+        (self.free)(ptr);
+        Ok(value)
+    }
+}
+
+impl PluginHost for NativePlugin {
+    fn check(&self, rule: &str, request: &str) -> Result<String> {
+        let invalid = |e: std::ffi::NulError| AnalyzerError::Plugin(e.to_string());
+        let rule = CString::new(rule).map_err(invalid)?;
+        let request = CString::new(request).map_err(invalid)?;
+
+        // SAFETY: both arguments are valid NUL-terminated strings that outlive the call,
+        // and the returned string is released through the plugin's own allocator.
+        unsafe {
+            let ptr = (self.check)(rule.as_ptr(), request.as_ptr());
+            self.take_string(ptr)
+        }
+    }
+}
+
+/// Load a plugin library, returning its declaration and host
+pub(super) fn load(path: &Path) -> Result<(String, Arc<dyn PluginHost>)> {
+    let display = path.display().to_string();
+    let plugin_error = |message: String| AnalyzerError::Plugin(format!("{display}: {message}"));
+
+    // SAFETY: loading a library runs its initialisers; plugins are trusted code listed
+    // explicitly in the project configuration.
+    let library = unsafe { libloading::Library::new(path) }
+        .map_err(|e| plugin_error(format!("failed to load plugin: {e}")))?;
+
+    // SAFETY: the symbol types match the plugin ABI declared above. The version check
+    // runs before any other symbol is used, so an incompatible plugin is never called
+    // with data it doesn't understand.
+    unsafe {
+        let missing = |symbol: &str| {
+            let symbol = symbol.to_string();
+            move |e: libloading::Error| {
+                plugin_error(format!("not an orlint plugin (missing {symbol}: {e})"))
+            }
+        };
+
+        let abi_version: AbiVersionFn = *library
+            .get(b"orlint_plugin_abi_version\0")
+            .map_err(missing("orlint_plugin_abi_version"))?;
+        check_compatibility(&display, abi_version(), None)?;
+
+        let declare: DeclareFn = *library
+            .get(b"orlint_plugin_declare\0")
+            .map_err(missing("orlint_plugin_declare"))?;
+        let check: CheckFn = *library
+            .get(b"orlint_plugin_check\0")
+            .map_err(missing("orlint_plugin_check"))?;
+        let free: FreeFn = *library
+            .get(b"orlint_plugin_free\0")
+            .map_err(missing("orlint_plugin_free"))?;
+
+        let plugin = NativePlugin {
+            path: display.clone(),
+            check,
+            free,
+            _library: library,
+        };
+        let declaration = plugin.take_string(declare())?;
+        Ok((declaration, Arc::new(plugin)))
+    }
+}
@@ -0,0 +1,233 @@
+// WebAssembly plugin host
+// Runs rule packs compiled to `wasm32-unknown-unknown` in an embedded interpreter.
+// Modules get no host functions at all, so they cannot reach the filesystem, network
+// or clock; memory growth and execution time are bounded per call.
+//
+// A module must export:
+//
+//   memory
+//   orlint_plugin_abi_version() -> i32
+//   orlint_alloc(len: i32) -> i32
+//   orlint_dealloc(ptr: i32, len: i32)
+//   orlint_plugin_declare() -> i64
+//   orlint_plugin_check(rule_ptr: i32, rule_len: i32, request_ptr: i32, request_len: i32) -> i64
+//
+// Strings are UTF-8 JSON in the module's memory. Returned strings are packed as
+// `(ptr << 32) | len` and released by the host with `orlint_dealloc`.
+//
+// An instance that fails a call (a trap, running out of fuel or a malformed result) may be
+// left in any state, so it is dropped and the next call starts from a fresh instance.
+
+use super::{check_compatibility, PluginHost};
+use crate::{AnalyzerError, Result};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use wasmi::{
+    Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+};
+
+/// Maximum linear memory a plugin may use
+const MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Execution budget for a single call into a plugin
+const FUEL_PER_CALL: u64 = 1_000_000_000;
+
+/// Largest string a plugin may return
+const STRING_LIMIT: usize = 16 * 1024 * 1024;
+
+/// A loaded WebAssembly plugin
+struct WasmPlugin {
+    path: String,
+    module: Module,
+    /// `None` after a failed call, until the next call instantiates the module again
+    instance: Mutex<Option<WasmInstance>>,
+}
+
+/// Instance state; calls need exclusive access to the store
+struct WasmInstance {
+    store: Store<StoreLimits>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    dealloc: TypedFunc<(i32, i32), ()>,
+    declare: TypedFunc<(), i64>,
+    check: TypedFunc<(i32, i32, i32, i32), i64>,
+}
+
+impl WasmInstance {
+    fn refuel(&mut self) -> std::result::Result<(), String> {
+        self.store
+            .set_fuel(FUEL_PER_CALL)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Copy a string into the module's memory
+    fn write_string(&mut self, value: &str) -> std::result::Result<(i32, i32), String> {
+        let len = i32::try_from(value.len()).map_err(|_| "string too large".to_string())?;
+        let ptr = self
+            .alloc
+            .call(&mut self.store, len)
+            .map_err(|e| e.to_string())?;
+        self.memory
+            .write(&mut self.store, ptr as u32 as usize, value.as_bytes())
+            .map_err(|e| e.to_string())?;
+        Ok((ptr, len))
+    }
+
+    /// Read a packed string returned by the module and release it
+    fn take_string(&mut self, packed: i64) -> std::result::Result<String, String> {
+        let (ptr, len) = ((packed as u64 >> 32) as u32, packed as u32);
+        // The length comes from the plugin, so check it before allocating anything
+        if len as usize > STRING_LIMIT {
+            return Err(format!(
+                "returned string of {len} bytes exceeds the limit of {STRING_LIMIT} bytes"
+            ));
+        }
+        let in_bounds = (ptr as usize)
+            .checked_add(len as usize)
+            .is_some_and(|end| end <= self.memory.data_size(&self.store));
+        if !in_bounds {
+            return Err(format!(
+                "returned string at {ptr} with length {len} is outside the plugin's memory"
+            ));
+        }
+        let mut buffer = vec![0; len as usize];
+        self.memory
+            .read(&self.store, ptr as usize, &mut buffer)
+            .map_err(|e| e.to_string())?;
+        self.dealloc
+            .call(&mut self.store, (ptr as i32, len as i32))
+            .map_err(|e| e.to_string())?;
+        String::from_utf8(buffer).map_err(|e| e.to_string())
+    }
+
+    fn declare(&mut self) -> std::result::Result<String, String> {
+        self.refuel()?;
+        let packed = self
+            .declare
+            .call(&mut self.store, ())
+            .map_err(|e| e.to_string())?;
+        self.take_string(packed)
+    }
+
+    fn check(&mut self, rule: &str, request: &str) -> std::result::Result<String, String> {
+        self.refuel()?;
+        let (rule_ptr, rule_len) = self.write_string(rule)?;
+        let (request_ptr, request_len) = self.write_string(request)?;
+        let packed = self
+            .check
+            .call(
+                &mut self.store,
+                (rule_ptr, rule_len, request_ptr, request_len),
+            )
+            .map_err(|e| e.to_string())?;
+        self.dealloc
+            .call(&mut self.store, (rule_ptr, rule_len))
+            .map_err(|e| e.to_string())?;
+        self.dealloc
+            .call(&mut self.store, (request_ptr, request_len))
+            .map_err(|e| e.to_string())?;
+        self.take_string(packed)
+    }
+}
+
+impl PluginHost for WasmPlugin {
+    fn check(&self, rule: &str, request: &str) -> Result<String> {
+        let plugin_error =
+            |message: String| AnalyzerError::Plugin(format!("{}: {message}", self.path));
+        let mut slot = self
+            .instance
+            .lock()
+            .map_err(|_| plugin_error("plugin panicked earlier".to_string()))?;
+        let mut instance = match slot.take() {
+            Some(instance) => instance,
+            None => instantiate(&self.path, &self.module)?,
+        };
+        let response = instance.check(rule, request).map_err(plugin_error)?;
+        // Only an instance whose call succeeded is kept for the next one
+        *slot = Some(instance);
+        Ok(response)
+    }
+}
+
+/// Load a WebAssembly plugin, returning its declaration and host
+pub(super) fn load(path: &Path) -> Result<(String, Arc<dyn PluginHost>)> {
+    let display = path.display().to_string();
+    let plugin_error = |message: String| AnalyzerError::Plugin(format!("{display}: {message}"));
+
+    let bytes =
+        std::fs::read(path).map_err(|e| plugin_error(format!("failed to read plugin: {e}")))?;
+
+    let mut config = wasmi::Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &bytes)
+        .map_err(|e| plugin_error(format!("invalid WebAssembly module: {e}")))?;
+
+    let mut wasm_instance = instantiate(&display, &module)?;
+    let declaration = wasm_instance.declare().map_err(plugin_error)?;
+    Ok((
+        declaration,
+        Arc::new(WasmPlugin {
+            path: display,
+            module,
+            instance: Mutex::new(Some(wasm_instance)),
+        }),
+    ))
+}
+
+/// Instantiate a plugin module with a fresh store, checking its ABI version and exports
+fn instantiate(path: &str, module: &Module) -> Result<WasmInstance> {
+    let plugin_error = |message: String| AnalyzerError::Plugin(format!("{path}: {message}"));
+
+    let mut store = Store::new(
+        module.engine(),
+        StoreLimitsBuilder::new().memory_size(MEMORY_LIMIT).build(),
+    );
+    store.limiter(|limits| limits);
+    store
+        .set_fuel(FUEL_PER_CALL)
+        .map_err(|e| plugin_error(e.to_string()))?;
+
+    // The linker is empty on purpose: any import (WASI, host calls) fails instantiation
+    let linker = Linker::<StoreLimits>::new(module.engine());
+    let instance: Instance = linker
+        .instantiate(&mut store, module)
+        .and_then(|pre| pre.start(&mut store))
+        .map_err(|e| {
+            plugin_error(format!(
+                "failed to instantiate plugin (plugins may not import host functions): {e}"
+            ))
+        })?;
+
+    let missing = |export: &str| {
+        let export = export.to_string();
+        move |e: wasmi::Error| plugin_error(format!("not an orlint plugin (missing {export}: {e})"))
+    };
+
+    let abi_version = instance
+        .get_typed_func::<(), i32>(&store, "orlint_plugin_abi_version")
+        .map_err(missing("orlint_plugin_abi_version"))?
+        .call(&mut store, ())
+        .map_err(|e| plugin_error(e.to_string()))?;
+    check_compatibility(path, abi_version as u32, None)?;
+
+    let memory = instance
+        .get_memory(&store, "memory")
+        .ok_or_else(|| plugin_error("not an orlint plugin (missing memory export)".to_string()))?;
+    Ok(WasmInstance {
+        alloc: instance
+            .get_typed_func(&store, "orlint_alloc")
+            .map_err(missing("orlint_alloc"))?,
+        dealloc: instance
+            .get_typed_func(&store, "orlint_dealloc")
+            .map_err(missing("orlint_dealloc"))?,
+        declare: instance
+            .get_typed_func(&store, "orlint_plugin_declare")
+            .map_err(missing("orlint_plugin_declare"))?,
+        check: instance
+            .get_typed_func(&store, "orlint_plugin_check")
+            .map_err(missing("orlint_plugin_check"))?,
+        memory,
+        store,
+    })
+}
@@ -0,0 +1,544 @@
+// Structural queries over template markup
+// A small selector language in the spirit of CSS selectors, used by declarative rules
+// and the `orlint query` command.
+//
+// Supported syntax:
+//   button, *, text            element by tag, any element, text/interpolation nodes
+//   [attr] [attr=value]        attribute presence and value (`=`, `~=`, `^=`, `$=`, `*=`)
+//   [attr=/regex/]             attribute value matching a regular expression
+//   a b, a > b                 descendant and child combinators
+//   :not(sel) :has(sel) :empty negation, relative `:has(> child)`, no content
+//   button$btn [@click=$fn]    capture an element or an attribute value
+//   a, b                       selector lists
+
+use crate::source::template::{Element, Node, Template};
+use crate::source::Span;
+use crate::{AnalyzerError, Result};
+use regex::Regex;
+
+/// A parsed structural query
+#[derive(Debug, Clone)]
+pub struct Query {
+    selectors: Vec<Selector>,
+}
+
+/// A single element matched by a query
+#[derive(Debug, Clone)]
+pub struct QueryMatch<'a> {
+    /// Matched element
+    pub element: &'a Element,
+    /// Values captured with `$name`
+    pub captures: Vec<Capture>,
+}
+
+/// A value captured by a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    /// Capture name without the `$`
+    pub name: String,
+    /// Captured text: the tag name for element captures, the value for attribute captures
+    pub text: String,
+    /// Span of the captured element's opening tag or attribute value
+    pub span: Span,
+}
+
+impl QueryMatch<'_> {
+    /// Look up a capture by name
+    pub fn capture(&self, name: &str) -> Option<&Capture> {
+        self.captures.iter().find(|c| c.name == name)
+    }
+}
+
+/// Complex selector: compounds joined by combinators, matched right to left
+#[derive(Debug, Clone)]
+struct Selector {
+    compounds: Vec<Compound>,
+    /// `combinators[i]` joins `compounds[i]` and `compounds[i + 1]`
+    combinators: Vec<Combinator>,
+    /// Relative selector inside `:has()` starting with `>`
+    leading_child: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone)]
+struct Compound {
+    node: NodeTest,
+    filters: Vec<Filter>,
+    capture: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum NodeTest {
+    Any,
+    Tag(String),
+    Text,
+}
+
+#[derive(Debug, Clone)]
+enum Filter {
+    Attribute {
+        name: String,
+        predicate: Option<ValuePredicate>,
+    },
+    Not(Vec<Selector>),
+    Has(Vec<Selector>),
+    Empty,
+}
+
+#[derive(Debug, Clone)]
+enum ValuePredicate {
+    Equals(String),
+    ContainsWord(String),
+    StartsWith(String),
+    EndsWith(String),
+    Contains(String),
+    Matches(Regex),
+    Capture(String),
+}
+
+/// Node a compound is tested against
+#[derive(Clone, Copy)]
+enum Target<'a> {
+    Element(&'a Element),
+    Text,
+}
+
+impl Query {
+    /// Parse a query pattern
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let mut parser = QueryParser {
+            input: pattern,
+            pos: 0,
+        };
+        let selectors = parser
+            .selector_list(false)
+            .and_then(|selectors| match parser.peek() {
+                None => Ok(selectors),
+                Some(c) => Err(format!("unexpected '{c}' at offset {}", parser.pos)),
+            })
+            .map_err(|e| AnalyzerError::Query(format!("Invalid query '{pattern}': {e}")))?;
+        Ok(Self { selectors })
+    }
+
+    /// Find all elements matching the query, in document order
+    pub fn find<'a>(&self, template: &'a Template) -> Vec<QueryMatch<'a>> {
+        let mut matches = vec![];
+        template.visit(&mut |node, ancestors| {
+            let Node::Element(element) = node else {
+                return;
+            };
+            for selector in &self.selectors {
+                let mut captures = vec![];
+                if selector.matches(Target::Element(element), ancestors, &mut captures) {
+                    matches.push(QueryMatch { element, captures });
+                    break;
+                }
+            }
+        });
+        matches
+    }
+}
+
+impl Selector {
+    fn matches(&self, target: Target, ancestors: &[&Element], captures: &mut Vec<Capture>) -> bool {
+        self.matches_at(self.compounds.len() - 1, target, ancestors, captures)
+    }
+
+    fn matches_at(
+        &self,
+        index: usize,
+        target: Target,
+        ancestors: &[&Element],
+        captures: &mut Vec<Capture>,
+    ) -> bool {
+        let mark = captures.len();
+        if !self.compounds[index].matches(target, ancestors, captures) {
+            captures.truncate(mark);
+            return false;
+        }
+        if index == 0 {
+            // `:has(> a b)` anchors the leftmost compound to a direct child
+            let anchored = !self.leading_child || ancestors.is_empty();
+            if !anchored {
+                captures.truncate(mark);
+            }
+            return anchored;
+        }
+
+        let found = match self.combinators[index - 1] {
+            Combinator::Child => match ancestors.split_last() {
+                Some((parent, rest)) => {
+                    self.matches_at(index - 1, Target::Element(parent), rest, captures)
+                }
+                None => false,
+            },
+            Combinator::Descendant => (0..ancestors.len()).rev().any(|i| {
+                let inner = captures.len();
+                let found = self.matches_at(
+                    index - 1,
+                    Target::Element(ancestors[i]),
+                    &ancestors[..i],
+                    captures,
+                );
+                if !found {
+                    captures.truncate(inner);
+                }
+                found
+            }),
+        };
+        if !found {
+            captures.truncate(mark);
+        }
+        found
+    }
+}
+
+impl Compound {
+    fn matches(&self, target: Target, ancestors: &[&Element], captures: &mut Vec<Capture>) -> bool {
+        let element = match (target, &self.node) {
+            (Target::Text, NodeTest::Text) => return self.filters.is_empty(),
+            (Target::Text, _) | (Target::Element(_), NodeTest::Text) => return false,
+            (Target::Element(element), NodeTest::Tag(tag))
+                if !element.tag.eq_ignore_ascii_case(tag) =>
+            {
+                return false
+            }
+            (Target::Element(element), _) => element,
+        };
+
+        for filter in &self.filters {
+            if !filter.matches(element, ancestors, captures) {
+                return false;
+            }
+        }
+
+        if let Some(name) = &self.capture {
+            captures.push(Capture {
+                name: name.clone(),
+                text: element.tag.clone(),
+                span: element.span,
+            });
+        }
+        true
+    }
+}
+
+impl Filter {
+    fn matches(
+        &self,
+        element: &Element,
+        ancestors: &[&Element],
+        captures: &mut Vec<Capture>,
+    ) -> bool {
+        match self {
+            Filter::Attribute { name, predicate } => {
+                let Some(attr) = element.attribute(name) else {
+                    return false;
+                };
+                let value = attr.value.as_deref().unwrap_or("");
+                match predicate {
+                    None => true,
+                    Some(ValuePredicate::Equals(v)) => value == v,
+                    Some(ValuePredicate::ContainsWord(v)) => {
+                        value.split_whitespace().any(|w| w == v)
+                    }
+                    Some(ValuePredicate::StartsWith(v)) => value.starts_with(v.as_str()),
+                    Some(ValuePredicate::EndsWith(v)) => value.ends_with(v.as_str()),
+                    Some(ValuePredicate::Contains(v)) => value.contains(v.as_str()),
+                    Some(ValuePredicate::Matches(regex)) => regex.is_match(value),
+                    Some(ValuePredicate::Capture(capture)) => {
+                        captures.push(Capture {
+                            name: capture.clone(),
+                            text: value.to_string(),
+                            span: attr.value_span.unwrap_or(attr.span),
+                        });
+                        true
+                    }
+                }
+            }
+            Filter::Not(selectors) => !selectors
+                .iter()
+                .any(|s| s.matches(Target::Element(element), ancestors, &mut vec![])),
+            Filter::Has(selectors) => selectors.iter().any(|s| has_match(s, element)),
+            Filter::Empty => element.children.iter().all(|child| match child {
+                Node::Text { text, .. } => text.trim().is_empty(),
+                Node::Comment { .. } => true,
+                _ => false,
+            }),
+        }
+    }
+}
+
+/// Whether a relative selector matches any node below `element`
+fn has_match(selector: &Selector, element: &Element) -> bool {
+    fn walk<'a>(nodes: &'a [Node], selector: &Selector, ancestors: &mut Vec<&'a Element>) -> bool {
+        for node in nodes {
+            let target = match node {
+                Node::Element(element) => Some(Target::Element(element)),
+                Node::Text { text, .. } if !text.trim().is_empty() => Some(Target::Text),
+                Node::Interpolation { .. } => Some(Target::Text),
+                _ => None,
+            };
+            if let Some(target) = target {
+                if selector.matches(target, ancestors, &mut vec![]) {
+                    return true;
+                }
+            }
+            if let Node::Element(child) = node {
+                ancestors.push(child);
+                let found = walk(&child.children, selector, ancestors);
+                ancestors.pop();
+                if found {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    walk(&element.children, selector, &mut vec![])
+}
+
+struct QueryParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+type ParseResult<T> = std::result::Result<T, String>;
+
+impl QueryParser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> ParseResult<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(format!("expected '{c}' at offset {}", self.pos))
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+        self.pos > start
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &str {
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn identifier(&mut self) -> ParseResult<String> {
+        let ident = self.take_while(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        if ident.is_empty() {
+            Err(format!("expected a name at offset {}", self.pos))
+        } else {
+            Ok(ident.to_string())
+        }
+    }
+
+    fn selector_list(&mut self, relative: bool) -> ParseResult<Vec<Selector>> {
+        let mut selectors = vec![self.selector(relative)?];
+        while self.eat(',') {
+            selectors.push(self.selector(relative)?);
+        }
+        Ok(selectors)
+    }
+
+    fn selector(&mut self, relative: bool) -> ParseResult<Selector> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let leading_child = relative && self.eat('>');
+        let mut compounds = vec![];
+        let mut combinators = vec![];
+
+        loop {
+            self.skip_whitespace();
+            compounds.push(self.compound()?);
+            let had_whitespace = self.skip_whitespace();
+            match self.peek() {
+                None | Some(',') | Some(')') => break,
+                Some('>') => {
+                    self.pos += 1;
+                    combinators.push(Combinator::Child);
+                }
+                Some(_) if had_whitespace => combinators.push(Combinator::Descendant),
+                Some(c) => return Err(format!("unexpected '{c}' at offset {}", self.pos)),
+            }
+        }
+
+        // Text nodes are only visited by `:has()`, and they have no children
+        let last = compounds.len() - 1;
+        let misplaced_text = compounds
+            .iter()
+            .enumerate()
+            .any(|(i, c)| matches!(c.node, NodeTest::Text) && !(relative && i == last));
+        if misplaced_text {
+            return Err(format!(
+                "'text' only matches as the last part of a ':has()' selector, at offset {start}"
+            ));
+        }
+
+        Ok(Selector {
+            compounds,
+            combinators,
+            leading_child,
+        })
+    }
+
+    fn compound(&mut self) -> ParseResult<Compound> {
+        let start = self.pos;
+        let node = if self.eat('*') {
+            NodeTest::Any
+        } else if self.peek().is_some_and(char::is_alphabetic) {
+            match self.identifier()?.as_str() {
+                "text" => NodeTest::Text,
+                tag => NodeTest::Tag(tag.to_string()),
+            }
+        } else {
+            NodeTest::Any
+        };
+
+        let mut filters = vec![];
+        let mut capture = None;
+        loop {
+            match self.peek() {
+                Some('[') => filters.push(self.attribute()?),
+                Some(':') => filters.push(self.pseudo_class()?),
+                Some('$') => {
+                    self.pos += 1;
+                    capture = Some(self.identifier()?);
+                }
+                _ => break,
+            }
+        }
+
+        if self.pos == start {
+            return Err(match self.peek() {
+                Some(c) => format!("expected a selector but found '{c}' at offset {}", self.pos),
+                None => "expected a selector".to_string(),
+            });
+        }
+        if matches!(node, NodeTest::Text) && (!filters.is_empty() || capture.is_some()) {
+            return Err(format!(
+                "'text' takes no attributes, pseudo-classes or captures at offset {start}"
+            ));
+        }
+
+        Ok(Compound {
+            node,
+            filters,
+            capture,
+        })
+    }
+
+    fn attribute(&mut self) -> ParseResult<Filter> {
+        self.expect('[')?;
+        self.skip_whitespace();
+        let name = self
+            .take_while(|c| !c.is_whitespace() && !"]=~^$*".contains(c))
+            .to_string();
+        if name.is_empty() {
+            return Err(format!("expected an attribute name at offset {}", self.pos));
+        }
+        self.skip_whitespace();
+
+        if self.eat(']') {
+            return Ok(Filter::Attribute {
+                name,
+                predicate: None,
+            });
+        }
+
+        let operator = ["=", "~=", "^=", "$=", "*="]
+            .into_iter()
+            .find(|op| self.input[self.pos..].starts_with(op))
+            .ok_or_else(|| format!("expected an attribute operator at offset {}", self.pos))?;
+        self.pos += operator.len();
+        self.skip_whitespace();
+
+        let predicate = match self.peek() {
+            Some('$') if operator == "=" => {
+                self.pos += 1;
+                ValuePredicate::Capture(self.identifier()?)
+            }
+            Some('/') if operator == "=" => {
+                self.pos += 1;
+                let pattern = self.take_while(|c| c != '/').to_string();
+                self.expect('/')?;
+                ValuePredicate::Matches(Regex::new(&pattern).map_err(|e| e.to_string())?)
+            }
+            _ => {
+                let value = self.value()?;
+                match operator {
+                    "=" => ValuePredicate::Equals(value),
+                    "~=" => ValuePredicate::ContainsWord(value),
+                    "^=" => ValuePredicate::StartsWith(value),
+                    "$=" => ValuePredicate::EndsWith(value),
+                    _ => ValuePredicate::Contains(value),
+                }
+            }
+        };
+
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(Filter::Attribute {
+            name,
+            predicate: Some(predicate),
+        })
+    }
+
+    fn value(&mut self) -> ParseResult<String> {
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                let value = self.take_while(|c| c != quote).to_string();
+                self.expect(quote)?;
+                Ok(value)
+            }
+            _ => Ok(self
+                .take_while(|c| !c.is_whitespace() && c != ']')
+                .to_string()),
+        }
+    }
+
+    fn pseudo_class(&mut self) -> ParseResult<Filter> {
+        self.expect(':')?;
+        let name = self.identifier()?;
+        match name.as_str() {
+            "not" | "has" => {
+                self.expect('(')?;
+                let selectors = self.selector_list(name == "has")?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                Ok(if name == "not" {
+                    Filter::Not(selectors)
+                } else {
+                    Filter::Has(selectors)
+                })
+            }
+            "empty" => Ok(Filter::Empty),
+            _ => Err(format!("unknown pseudo-class ':{name}'")),
+        }
+    }
+}
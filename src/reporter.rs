@@ -1,4 +1,5 @@
 // Reporter for lint issues
+use crate::fix::Fix;
 use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{self, Write};
@@ -59,6 +60,9 @@ pub struct Issue {
     pub column: usize,
     /// Severity of the issue
     pub severity: Severity,
+    /// Automatic fix for the issue, if the rule can provide one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
 }
 
 /// Reporter for lint issues
@@ -0,0 +1,1452 @@
+// Accessibility rules for the component's template
+// These check alt text, accessible names, ARIA roles and attributes, form labels,
+// keyboard access, color contrast and page structure, using the built-in WAI-ARIA table in the `aria`
+// module and the colors resolved from the style section.
+
+use crate::config::WcagLevel;
+use crate::fix::{Edit, Fix};
+use crate::reporter::{Issue, Severity};
+use crate::rules::aria::{self, ABSTRACT_ROLES, ROLES};
+use crate::rules::color::{parse_color, Color};
+use crate::rules::{closest_match, issue_at, words, Rule};
+use crate::source::style::{self, Declaration, Selector};
+use crate::source::template::{Attribute, Element, Node};
+use crate::source::{SourceFile, Span};
+use orbit::parser::OrbitAst;
+use regex::Regex;
+use std::collections::HashSet;
+
+/// Rule for images without alt text
+pub struct MissingAltRule;
+
+impl Rule for MissingAltRule {
+    fn name(&self) -> &'static str {
+        "a11y-missing-alt"
+    }
+
+    fn description(&self) -> &'static str {
+        "Images should have alt text; decorative images should use alt=\"\""
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        Ok(visible_elements(source)
+            .into_iter()
+            .filter(|element| {
+                let needs_alt = match element.tag.as_str() {
+                    "img" => true,
+                    "area" => has_attribute(element, "href"),
+                    "input" => static_value(element, "type") == Some("image"),
+                    _ => false,
+                };
+                needs_alt
+                    && !matches!(static_value(element, "role"), Some("presentation" | "none"))
+                    && !["alt", "aria-label", "aria-labelledby"]
+                        .iter()
+                        .any(|name| has_attribute(element, name))
+            })
+            .map(|element| {
+                issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    element.span,
+                    Severity::Error,
+                    format!(
+                        "<{}> is missing alt text; describe the image in alt, or use alt=\"\" if it is decorative",
+                        element.tag
+                    ),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Rule for buttons and links that screen readers can't announce
+pub struct AccessibleNameRule;
+
+impl Rule for AccessibleNameRule {
+    fn name(&self) -> &'static str {
+        "a11y-accessible-name"
+    }
+
+    fn description(&self) -> &'static str {
+        "Buttons and links should have an accessible name from their content or an aria-label"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        Ok(visible_elements(source)
+            .into_iter()
+            .filter(|element| {
+                let is_input_button =
+                    element.tag == "input" && static_value(element, "type") == Some("button");
+                let needs_name = element.tag == "button"
+                    || (element.tag == "a" && has_attribute(element, "href"))
+                    || is_input_button
+                    || matches!(static_value(element, "role"), Some("button" | "link"));
+
+                let named = has_name_attribute(element)
+                    || (is_input_button && has_attribute(element, "value"))
+                    || has_content(&element.children);
+                needs_name && !named
+            })
+            .map(|element| {
+                issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    element.span,
+                    Severity::Error,
+                    format!(
+                        "<{}> has no accessible name; add text content or an aria-label",
+                        element.tag
+                    ),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Rule for `role` values that aren't concrete WAI-ARIA roles
+pub struct ValidRoleRule;
+
+impl Rule for ValidRoleRule {
+    fn name(&self) -> &'static str {
+        "a11y-valid-role"
+    }
+
+    fn description(&self) -> &'static str {
+        "role attributes should name concrete WAI-ARIA roles"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+
+        for element in source.template().elements() {
+            let Some(attribute) = element.attribute("role") else {
+                continue;
+            };
+            let (Some(value), Some(value_span)) = (&attribute.value, attribute.value_span) else {
+                continue;
+            };
+
+            if value.trim().is_empty() {
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    attribute.span,
+                    Severity::Error,
+                    "Empty role attribute; remove it or name a WAI-ARIA role".to_string(),
+                ));
+                continue;
+            }
+
+            // A role attribute may list fallback roles separated by spaces
+            for (offset, role) in words(value) {
+                let span = Span::new(
+                    value_span.start + offset,
+                    value_span.start + offset + role.len(),
+                );
+                if ABSTRACT_ROLES.contains(&role) {
+                    issues.push(issue_at(
+                        self.name(),
+                        source,
+                        file_path,
+                        span,
+                        Severity::Error,
+                        format!("Role '{role}' is abstract and can't be used on elements"),
+                    ));
+                } else if aria::find_role(role).is_none() {
+                    let suggestion = closest_match(role, ROLES.iter().map(|r| r.name));
+                    let mut issue = issue_at(
+                        self.name(),
+                        source,
+                        file_path,
+                        span,
+                        Severity::Error,
+                        match suggestion {
+                            Some(suggestion) => {
+                                format!("Unknown ARIA role '{role}'; did you mean '{suggestion}'?")
+                            }
+                            None => format!("Unknown ARIA role '{role}'"),
+                        },
+                    );
+                    issue.fix = suggestion.map(|suggestion| {
+                        Fix::new(
+                            format!("Replace role '{role}' with '{suggestion}'"),
+                            vec![Edit::replace(span, suggestion)],
+                        )
+                    });
+                    issues.push(issue);
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for unknown ARIA attributes, attributes a role doesn't support and missing required ones
+pub struct RoleAttributesRule;
+
+impl Rule for RoleAttributesRule {
+    fn name(&self) -> &'static str {
+        "a11y-role-attributes"
+    }
+
+    fn description(&self) -> &'static str {
+        "ARIA attributes should exist and be supported by the element's role, and roles should have their required attributes"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+        let mut report = |span: Span, message: String, fix: Option<Fix>| {
+            let mut issue = issue_at(
+                self.name(),
+                source,
+                file_path,
+                span,
+                Severity::Error,
+                message,
+            );
+            issue.fix = fix;
+            issues.push(issue);
+        };
+
+        for element in source.template().elements() {
+            let role_attribute = element.attribute("role");
+            // The first role the table knows is the one browsers use
+            let explicit = role_attribute
+                .and_then(|a| a.value.as_deref())
+                .and_then(|value| words(value).find_map(|(_, role)| aria::find_role(role)));
+            let role = match explicit {
+                Some(role) => Some((role, format!("role '{}'", role.name))),
+                // A bound role can't be checked, and components render their own markup
+                None if has_attribute(element, "role") || is_component(element) => None,
+                None => aria::implicit_role(element)
+                    .and_then(aria::find_role)
+                    .map(|role| {
+                        (
+                            role,
+                            format!("<{}> (implicit role '{}')", element.tag, role.name),
+                        )
+                    }),
+            };
+
+            for attribute in &element.attributes {
+                let prefix = if attribute.name.starts_with(':') {
+                    ":"
+                } else if attribute.name.starts_with("v-bind:") {
+                    "v-bind:"
+                } else {
+                    ""
+                };
+                let name = &attribute.name[prefix.len()..];
+                if !name.starts_with("aria-") {
+                    continue;
+                }
+                let name_span = Span::new(
+                    attribute.span.start + prefix.len(),
+                    attribute.span.start + attribute.name.len(),
+                );
+
+                if !aria::attributes().any(|known| known == name) {
+                    let suggestion = closest_match(name, aria::attributes());
+                    report(
+                        name_span,
+                        match suggestion {
+                            Some(suggestion) => format!(
+                                "Unknown ARIA attribute '{name}'; did you mean '{suggestion}'?"
+                            ),
+                            None => format!("Unknown ARIA attribute '{name}'"),
+                        },
+                        suggestion.map(|suggestion| {
+                            Fix::new(
+                                format!("Rename '{name}' to '{suggestion}'"),
+                                vec![Edit::replace(name_span, suggestion)],
+                            )
+                        }),
+                    );
+                } else if let Some((role, described)) =
+                    role.as_ref().filter(|(r, _)| !r.supports(name))
+                {
+                    let reason = if role.naming_prohibited
+                        && matches!(name, "aria-label" | "aria-labelledby")
+                    {
+                        ", which can't be named"
+                    } else {
+                        ""
+                    };
+                    report(
+                        name_span,
+                        format!("ARIA attribute '{name}' is not supported by {described}{reason}"),
+                        None,
+                    );
+                }
+            }
+
+            // Native elements provide the required states themselves, so only explicit roles need them
+            if let (Some(role), Some(role_attribute)) = (explicit, role_attribute) {
+                for required in role.required {
+                    if !has_attribute(element, required) {
+                        report(
+                            role_attribute.span,
+                            format!("Role '{}' requires '{required}'", role.name),
+                            None,
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for form controls without an associated label
+pub struct FormLabelRule;
+
+impl Rule for FormLabelRule {
+    fn name(&self) -> &'static str {
+        "a11y-form-label"
+    }
+
+    fn description(&self) -> &'static str {
+        "Form controls should have a label, through a <label> element or an aria-label"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let template = source.template();
+        let labels: Vec<&Element> = template
+            .elements()
+            .into_iter()
+            .filter(|e| e.tag == "label")
+            .collect();
+        let label_targets: Vec<&str> = labels
+            .iter()
+            .filter_map(|label| static_value(label, "for"))
+            .collect();
+        let bound_targets = labels
+            .iter()
+            .any(|label| has_attribute(label, "for") && static_value(label, "for").is_none());
+
+        let mut issues = vec![];
+        template.visit(&mut |node, ancestors| {
+            let Node::Element(element) = node else {
+                return;
+            };
+            let is_control = match element.tag.as_str() {
+                "input" => !matches!(
+                    static_value(element, "type"),
+                    Some("hidden" | "submit" | "reset" | "button" | "image")
+                ),
+                "select" | "textarea" => true,
+                _ => false,
+            };
+            if !is_control
+                || is_hidden(element)
+                || ancestors.iter().any(|a| is_hidden(a) || is_component(a) || a.tag == "label")
+                || ["aria-label", "aria-labelledby", "title"]
+                    .iter()
+                    .any(|name| has_attribute(element, name))
+            {
+                return;
+            }
+
+            let labelled = match static_value(element, "id") {
+                Some(id) => label_targets.contains(&id),
+                // A bound id may match a bound `for`
+                None => has_attribute(element, "id") && bound_targets,
+            };
+            if !labelled {
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    element.span,
+                    Severity::Warning,
+                    format!(
+                        "<{}> has no label; wrap it in a <label>, point a <label for> at its id, or add an aria-label",
+                        element.tag
+                    ),
+                ));
+            }
+        });
+
+        Ok(issues)
+    }
+}
+
+/// Rule for click handlers that keyboard users can't trigger
+pub struct ClickKeyboardRule;
+
+impl Rule for ClickKeyboardRule {
+    fn name(&self) -> &'static str {
+        "a11y-click-keyboard"
+    }
+
+    fn description(&self) -> &'static str {
+        "Click handlers on non-interactive elements need a keyboard handler and a tabindex"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+
+        for element in visible_elements(source) {
+            let events: Vec<&str> = element.attributes.iter().filter_map(event_name).collect();
+            if !events.contains(&"click") || is_component(element) || is_interactive(element) {
+                continue;
+            }
+            let has_key_handler = events
+                .iter()
+                .any(|event| matches!(*event, "keydown" | "keyup" | "keypress"));
+            let focusable = match tabindex(element) {
+                Some(TabIndex::Static(index)) => index >= 0,
+                Some(TabIndex::Bound) => true,
+                None => false,
+            };
+
+            let tag = &element.tag;
+            let (message, fix) = match (has_key_handler, focusable) {
+                (true, true) => continue,
+                (false, false) => (
+                    format!("Click handler on <{tag}> has no keyboard equivalent; use a <button>, or add a @keydown handler and tabindex=\"0\""),
+                    None,
+                ),
+                (false, true) => (
+                    format!("Click handler on <{tag}> has no matching keyboard handler; add @keydown or @keyup so keyboard users can trigger it"),
+                    None,
+                ),
+                (true, false) => (
+                    format!("<{tag}> has click and keyboard handlers but can't receive focus; add tabindex=\"0\""),
+                    // Without any tabindex, one can be added after the tag name
+                    tabindex(element).is_none().then(|| {
+                        Fix::new(
+                            "Add tabindex=\"0\"",
+                            vec![Edit::insert(
+                                element.span.start + 1 + tag.len(),
+                                " tabindex=\"0\"",
+                            )],
+                        )
+                    }),
+                ),
+            };
+
+            let mut issue = issue_at(
+                self.name(),
+                source,
+                file_path,
+                element.span,
+                Severity::Warning,
+                message,
+            );
+            issue.fix = fix;
+            issues.push(issue);
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for tabindex values above zero
+pub struct PositiveTabindexRule;
+
+impl Rule for PositiveTabindexRule {
+    fn name(&self) -> &'static str {
+        "a11y-positive-tabindex"
+    }
+
+    fn description(&self) -> &'static str {
+        "tabindex should be 0 or -1; positive values override the document's tab order"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+
+        for element in source.template().elements() {
+            for attribute in &element.attributes {
+                if !matches!(
+                    attribute.name.as_str(),
+                    "tabindex" | ":tabindex" | "v-bind:tabindex"
+                ) {
+                    continue;
+                }
+                let (Some(value), Some(value_span)) = (&attribute.value, attribute.value_span)
+                else {
+                    continue;
+                };
+                let Some(index) = value.trim().parse::<i64>().ok().filter(|&i| i > 0) else {
+                    continue;
+                };
+
+                let mut issue = issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    attribute.span,
+                    Severity::Warning,
+                    format!("tabindex=\"{index}\" moves <{}> ahead of the document's tab order; use 0 and order the markup instead", element.tag),
+                );
+                issue.fix = Some(Fix::new(
+                    "Use tabindex=\"0\"",
+                    vec![Edit::replace(value_span, "0")],
+                ));
+                issues.push(issue);
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for `autofocus`, which moves focus without the user asking
+pub struct NoAutofocusRule;
+
+impl Rule for NoAutofocusRule {
+    fn name(&self) -> &'static str {
+        "a11y-no-autofocus"
+    }
+
+    fn description(&self) -> &'static str {
+        "autofocus should not be used; it moves focus and scrolls the page unexpectedly"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+
+        for element in source.template().elements() {
+            for attribute in &element.attributes {
+                let fix = match attribute.name.as_str() {
+                    "autofocus" => Some(Fix::new(
+                        "Remove autofocus",
+                        vec![Edit::delete(with_leading_space(source, attribute.span))],
+                    )),
+                    ":autofocus" | "v-bind:autofocus"
+                        if attribute.value.as_deref().map(str::trim) != Some("false") =>
+                    {
+                        None
+                    }
+                    _ => continue,
+                };
+
+                let mut issue = issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    attribute.span,
+                    Severity::Warning,
+                    format!(
+                        "autofocus on <{}> moves focus without the user asking; let users move focus themselves",
+                        element.tag
+                    ),
+                );
+                issue.fix = fix;
+                issues.push(issue);
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for focusable elements inside content hidden with `aria-hidden`
+pub struct HiddenFocusableRule;
+
+impl Rule for HiddenFocusableRule {
+    fn name(&self) -> &'static str {
+        "a11y-hidden-focusable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Elements hidden with aria-hidden should not be focusable"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+
+        source.template().visit(&mut |node, ancestors| {
+            let Node::Element(element) = node else {
+                return;
+            };
+            let aria_hidden = |e: &Element| static_value(e, "aria-hidden") == Some("true");
+            // `hidden` and `inert` content can't be focused at all
+            let unreachable = |e: &Element| e.has_attribute("hidden") || e.has_attribute("inert");
+
+            let in_hidden = aria_hidden(element) || ancestors.iter().any(|a| aria_hidden(a));
+            if !in_hidden
+                || unreachable(element)
+                || ancestors.iter().any(|a| unreachable(a))
+                || is_component(element)
+            {
+                return;
+            }
+
+            let focusable = match tabindex(element) {
+                Some(TabIndex::Static(index)) => index >= 0,
+                // A bound tabindex may well be -1
+                Some(TabIndex::Bound) => false,
+                None => is_interactive(element) && !has_attribute(element, "disabled"),
+            };
+            if focusable {
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    element.span,
+                    Severity::Error,
+                    format!(
+                        "<{}> is hidden with aria-hidden but can still receive focus; add tabindex=\"-1\" or move it out of the hidden content",
+                        element.tag
+                    ),
+                ));
+            }
+        });
+
+        Ok(issues)
+    }
+}
+
+/// Pseudo-classes that don't depend on user interaction, so rules using them apply to the resting state
+const STRUCTURAL_PSEUDO_CLASSES: &[&str] = &[
+    "first-child",
+    "last-child",
+    "only-child",
+    "nth-child",
+    "nth-last-child",
+    "first-of-type",
+    "last-of-type",
+    "only-of-type",
+    "nth-of-type",
+    "nth-last-of-type",
+    "not",
+    "is",
+    "where",
+    "matches",
+    "empty",
+    "deep",
+    "global",
+    "slotted",
+];
+
+/// Properties that decide text contrast
+const CONTRAST_PROPERTIES: &[&str] = &[
+    "color",
+    "background",
+    "background-color",
+    "background-image",
+    "font-size",
+    "font-weight",
+];
+
+/// Rule for text whose color doesn't contrast enough with its background
+pub struct ColorContrastRule {
+    level: WcagLevel,
+}
+
+impl ColorContrastRule {
+    pub fn new(level: WcagLevel) -> Self {
+        Self { level }
+    }
+}
+
+impl Default for ColorContrastRule {
+    fn default() -> Self {
+        Self::new(WcagLevel::default())
+    }
+}
+
+impl Rule for ColorContrastRule {
+    fn name(&self) -> &'static str {
+        "a11y-color-contrast"
+    }
+
+    fn description(&self) -> &'static str {
+        "Text colors should meet the WCAG contrast ratio against their background"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        // Rules inside @media and friends, and state rules such as :hover, don't describe
+        // the resting state, so only unconditional rules take part in the cascade
+        let mut candidates = vec![];
+        for rule in &source.stylesheet().rules {
+            if !rule.conditions.is_empty() {
+                continue;
+            }
+            for selector in rule.selectors.iter().filter(|s| is_resting(s)) {
+                for declaration in &rule.declarations {
+                    let property = declaration.property.to_lowercase();
+                    if CONTRAST_PROPERTIES.contains(&property.as_str()) {
+                        candidates.push((selector, declaration));
+                    }
+                }
+            }
+        }
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut check = ContrastCheck {
+            rule: self,
+            source,
+            file_path,
+            candidates,
+            reported: HashSet::new(),
+            issues: vec![],
+        };
+        check.walk(&source.template().nodes, &mut vec![], TextStyle::default());
+        Ok(check.issues)
+    }
+}
+
+/// A color resolved from the style section
+#[derive(Debug, Clone, Copy)]
+enum Paint {
+    /// Nothing is declared, so the color depends on where the component is used
+    Unset,
+    /// Declared with a value that can't be resolved statically (`var()`, images, ...)
+    Unknown,
+    /// A color and the span of the declaration value that set it
+    Known(Color, Span),
+}
+
+/// Inherited text style of an element
+#[derive(Debug, Clone, Copy)]
+struct TextStyle {
+    color: Paint,
+    background: Paint,
+    font_size: f64,
+    bold: bool,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            color: Paint::Unset,
+            background: Paint::Unset,
+            font_size: 16.0,
+            bold: false,
+        }
+    }
+}
+
+impl TextStyle {
+    /// Whether the text counts as large, which lowers the required contrast ratio
+    fn is_large(&self) -> bool {
+        self.font_size >= 24.0 || (self.bold && self.font_size >= 18.66)
+    }
+}
+
+/// Cascade order of a declaration: importance, inline style, specificity, then source order
+type Precedence = (bool, bool, (usize, usize, usize), usize);
+
+struct ContrastCheck<'a> {
+    rule: &'a ColorContrastRule,
+    source: &'a SourceFile,
+    file_path: &'a str,
+    candidates: Vec<(&'a Selector, &'a Declaration)>,
+    /// (color, background) declaration pairs already reported
+    reported: HashSet<(Span, Span)>,
+    issues: Vec<Issue>,
+}
+
+impl<'a> ContrastCheck<'a> {
+    fn walk(&mut self, nodes: &'a [Node], ancestors: &mut Vec<&'a Element>, parent: TextStyle) {
+        for node in nodes {
+            let Node::Element(element) = node else {
+                continue;
+            };
+            if is_hidden(element) {
+                continue;
+            }
+            if is_component(element) {
+                // A component may paint its own background behind slot content
+                let style = TextStyle {
+                    background: Paint::Unknown,
+                    ..parent
+                };
+                ancestors.push(element);
+                self.walk(&element.children, ancestors, style);
+                ancestors.pop();
+                continue;
+            }
+
+            let style = self.text_style(element, ancestors, parent);
+            if has_text(element) && !has_attribute(element, "disabled") {
+                self.check(element, &style);
+            }
+            ancestors.push(element);
+            self.walk(&element.children, ancestors, style);
+            ancestors.pop();
+        }
+    }
+
+    /// Resolve the element's style from the winning declarations for each property
+    fn text_style(
+        &self,
+        element: &Element,
+        ancestors: &[&Element],
+        parent: TextStyle,
+    ) -> TextStyle {
+        let inline = element
+            .attribute("style")
+            .and_then(|a| a.value_span)
+            .map(|span| style::parse_declarations(self.source.text(), span))
+            .unwrap_or_default();
+
+        let mut winners: Vec<(&str, Precedence, &Declaration)> = vec![];
+        let matched = self
+            .candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, (selector, _))| selector.matches(element, ancestors))
+            .map(|(order, (selector, declaration))| {
+                ((false, selector.specificity(), order), *declaration)
+            });
+        let inline_declarations = inline
+            .iter()
+            .enumerate()
+            .map(|(order, declaration)| ((true, (0, 0, 0), order), declaration));
+        for ((inline, specificity, order), declaration) in matched.chain(inline_declarations) {
+            let property = declaration.property.to_lowercase();
+            // `background` and `background-color` both set the background color
+            let slot = match property.as_str() {
+                "background" => "background-color",
+                property => match CONTRAST_PROPERTIES.iter().find(|p| **p == property) {
+                    Some(slot) => slot,
+                    None => continue,
+                },
+            };
+            let key = (declaration.important, inline, specificity, order);
+            match winners.iter_mut().find(|(s, _, _)| *s == slot) {
+                Some(winner) if winner.1 <= key => *winner = (slot, key, declaration),
+                Some(_) => {}
+                None => winners.push((slot, key, declaration)),
+            }
+        }
+
+        let mut style = parent;
+        // Headings and strong text are bold by default, and the top headings larger
+        if matches!(
+            element.tag.as_str(),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" | "b" | "strong"
+        ) {
+            style.bold = true;
+        }
+        style.font_size *= match element.tag.as_str() {
+            "h1" => 2.0,
+            "h2" => 1.5,
+            "h3" => 1.17,
+            _ => 1.0,
+        };
+
+        for (slot, _, declaration) in winners {
+            let value = declaration.value.trim().to_lowercase();
+            match slot {
+                "color" => style.color = text_color(&value, declaration, parent.color),
+                "background-color" => {
+                    style.background = background(&value, declaration, parent.background)
+                }
+                "background-image" if value != "none" => style.background = Paint::Unknown,
+                "font-size" => {
+                    if let Some(size) = font_size(&value, parent.font_size) {
+                        style.font_size = size;
+                    }
+                }
+                "font-weight" => match value.as_str() {
+                    "bold" | "bolder" => style.bold = true,
+                    "normal" | "lighter" => style.bold = false,
+                    weight => {
+                        if let Ok(weight) = weight.parse::<f64>() {
+                            style.bold = weight >= 700.0;
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+        style
+    }
+
+    fn check(&mut self, element: &Element, style: &TextStyle) {
+        let (Paint::Known(color, color_span), Paint::Known(background, background_span)) =
+            (style.color, style.background)
+        else {
+            return;
+        };
+        let color = if color.is_opaque() {
+            color
+        } else {
+            color.over(background)
+        };
+        let ratio = color.contrast(background);
+
+        let (level, normal, large) = match self.rule.level {
+            WcagLevel::Aa => ("AA", 4.5, 3.0),
+            WcagLevel::Aaa => ("AAA", 7.0, 4.5),
+        };
+        let (minimum, size) = if style.is_large() {
+            (large, "large text")
+        } else {
+            (normal, "normal text")
+        };
+        if ratio >= minimum || !self.reported.insert((color_span, background_span)) {
+            return;
+        }
+
+        let source = self.source;
+        self.issues.push(issue_at(
+            self.rule.name(),
+            source,
+            self.file_path,
+            element.span,
+            Severity::Error,
+            format!(
+                "Text color '{}' on background '{}' in <{}> has a contrast ratio of {:.2}:1, below the WCAG {} minimum of {}:1 for {}; the color is set on line {}, the background on line {}",
+                source.slice(color_span),
+                source.slice(background_span),
+                element.tag,
+                // Round down so a failing ratio is never displayed as the minimum
+                (ratio * 100.0).floor() / 100.0,
+                level,
+                minimum,
+                size,
+                source.position(color_span.start).0,
+                source.position(background_span.start).0,
+            ),
+        ));
+    }
+}
+
+/// Whether every compound of the selector applies without user interaction
+fn is_resting(selector: &Selector) -> bool {
+    selector.compounds.iter().all(|compound| {
+        compound.pseudo_element.is_none()
+            && compound
+                .pseudo_classes
+                .iter()
+                .all(|p| STRUCTURAL_PSEUDO_CLASSES.contains(&p.name.as_str()))
+    })
+}
+
+fn text_color(value: &str, declaration: &Declaration, parent: Paint) -> Paint {
+    if matches!(value, "inherit" | "currentcolor" | "unset") {
+        return parent;
+    }
+    match parse_color(value) {
+        // Invisible text has nothing to contrast
+        Some(color) if color.a == 0.0 => Paint::Unknown,
+        Some(color) => Paint::Known(color, declaration.value_span),
+        None => Paint::Unknown,
+    }
+}
+
+/// Resolve a `background` or `background-color` value painted over the parent's background
+fn background(value: &str, declaration: &Declaration, parent: Paint) -> Paint {
+    if value.contains("url(") || value.contains("gradient(") || value.contains("var(") {
+        return Paint::Unknown;
+    }
+    let color = parse_color(value).or_else(|| {
+        // The color in a `background` shorthand such as `#fff no-repeat`
+        value.split_whitespace().filter_map(parse_color).next()
+    });
+    match color {
+        Some(color) if color.a == 0.0 => parent,
+        Some(color) if color.is_opaque() => Paint::Known(color, declaration.value_span),
+        Some(color) => match parent {
+            Paint::Known(below, _) => Paint::Known(color.over(below), declaration.value_span),
+            _ => Paint::Unknown,
+        },
+        None if value == "currentcolor" => Paint::Unknown,
+        // `none`, `inherit` and shorthands without a color show the parent's background
+        None => parent,
+    }
+}
+
+/// Resolve a `font-size` value in pixels
+fn font_size(value: &str, parent: f64) -> Option<f64> {
+    let number = |suffix: &str| value.strip_suffix(suffix)?.trim().parse::<f64>().ok();
+    number("px")
+        .or_else(|| number("pt").map(|pt| pt * 4.0 / 3.0))
+        .or_else(|| number("rem").map(|rem| rem * 16.0))
+        .or_else(|| number("em").map(|em| em * parent))
+        .or_else(|| number("%").map(|percent| percent / 100.0 * parent))
+}
+
+/// Whether the element has text of its own, rather than only through child elements
+fn has_text(element: &Element) -> bool {
+    element.children.iter().any(|node| match node {
+        Node::Text { text, .. } => !text.trim().is_empty(),
+        Node::Interpolation { .. } => true,
+        _ => false,
+    })
+}
+
+/// Rule for headings that skip levels
+pub struct HeadingOrderRule;
+
+impl Rule for HeadingOrderRule {
+    fn name(&self) -> &'static str {
+        "a11y-heading-order"
+    }
+
+    fn description(&self) -> &'static str {
+        "Heading levels should not skip levels"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+        let mut previous = None;
+
+        for element in visible_elements(source) {
+            let Some(level) = heading_level(element) else {
+                continue;
+            };
+            // The first heading may start at any level, since the component can be nested
+            if let Some(previous) = previous.filter(|previous| level > previous + 1) {
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    element.span,
+                    Severity::Warning,
+                    format!(
+                        "Heading level jumps from h{previous} to h{level}; use <h{}> here so the outline has no gaps",
+                        previous + 1
+                    ),
+                ));
+            }
+            previous = Some(level);
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for pages with more than one `<h1>`
+pub struct SingleH1Rule {
+    pages: Pages,
+}
+
+impl SingleH1Rule {
+    pub fn new(pages: Vec<String>) -> Self {
+        Self {
+            pages: Pages::new(&pages),
+        }
+    }
+}
+
+impl Default for SingleH1Rule {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+impl Rule for SingleH1Rule {
+    fn name(&self) -> &'static str {
+        "a11y-single-h1"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pages should have at most one <h1>"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        if !self.pages.contains(source, file_path) {
+            return Ok(vec![]);
+        }
+
+        let headings: Vec<_> = branched_elements(source)
+            .into_iter()
+            .filter(|(element, _)| heading_level(element) == Some(1))
+            .collect();
+        Ok(repeated(&headings)
+            .map(|(element, first)| {
+                issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    element.span,
+                    Severity::Warning,
+                    format!(
+                        "Page already has an <h1> on line {}; use <h2> or lower for section headings",
+                        source.position(first.span.start).0
+                    ),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Rule for pages without exactly one main landmark
+pub struct PageLandmarksRule {
+    pages: Pages,
+}
+
+impl PageLandmarksRule {
+    pub fn new(pages: Vec<String>) -> Self {
+        Self {
+            pages: Pages::new(&pages),
+        }
+    }
+}
+
+impl Default for PageLandmarksRule {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+
+impl Rule for PageLandmarksRule {
+    fn name(&self) -> &'static str {
+        "a11y-page-landmarks"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pages should have exactly one main landmark"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        if !self.pages.contains(source, file_path) {
+            return Ok(vec![]);
+        }
+
+        let elements = branched_elements(source);
+        let mains: Vec<_> = elements
+            .iter()
+            .filter(|(element, _)| element_role(element) == Some("main"))
+            .cloned()
+            .collect();
+
+        let mut issues: Vec<_> = repeated(&mains)
+            .map(|(element, first)| {
+                issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    element.span,
+                    Severity::Warning,
+                    format!(
+                        "Page already has a main landmark on line {}; a page should have exactly one",
+                        source.position(first.span.start).0
+                    ),
+                )
+            })
+            .collect();
+
+        // A component wrapping the page's content, such as a layout, may provide <main>
+        let wrapped = elements
+            .iter()
+            .any(|(element, _)| is_component(element) && element.child_elements().next().is_some());
+        let first = source.template().nodes.iter().find_map(|node| match node {
+            Node::Element(element) => Some(element),
+            _ => None,
+        });
+        if let (true, false, Some(first)) = (mains.is_empty(), wrapped, first) {
+            issues.push(issue_at(
+                self.name(),
+                source,
+                file_path,
+                first.span,
+                Severity::Warning,
+                "Page has no main landmark; wrap its primary content in <main> so screen reader users can jump to it".to_string(),
+            ));
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Page-level components, marked by `<page>` metadata or matched by file path globs
+struct Pages {
+    patterns: Vec<Regex>,
+}
+
+impl Pages {
+    fn new(patterns: &[String]) -> Self {
+        Self {
+            patterns: patterns.iter().map(|p| glob_regex(p)).collect(),
+        }
+    }
+
+    /// Whether the file is a page; `<page>false</page>` opts out a file matched by a glob
+    fn contains(&self, source: &SourceFile, file_path: &str) -> bool {
+        match source.metadata("page") {
+            Some(value) => value != "false",
+            None => {
+                let path = file_path.replace('\\', "/");
+                self.patterns.iter().any(|pattern| pattern.is_match(&path))
+            }
+        }
+    }
+}
+
+/// Translate a glob into a regex matching a whole path, or the end of one after a `/`
+fn glob_regex(glob: &str) -> Regex {
+    let mut regex = String::from("(?:^|/)");
+    let mut chars = glob.trim_start_matches("./").chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.next_if_eq(&'/').is_some() {
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).expect("glob characters are escaped")
+}
+
+/// Level of an `<h1>`-`<h6>` or `role="heading"` element
+fn heading_level(element: &Element) -> Option<u32> {
+    match element.tag.as_bytes() {
+        [b'h', level @ b'1'..=b'6'] => Some(u32::from(level - b'0')),
+        _ if element_role(element) == Some("heading") => Some(
+            static_value(element, "aria-level")
+                .and_then(|level| level.trim().parse().ok())
+                .unwrap_or(2),
+        ),
+        _ => None,
+    }
+}
+
+/// Role of an element: the first known role in `role`, or its implicit role
+fn element_role(element: &Element) -> Option<&'static str> {
+    match element.attribute("role").and_then(|a| a.value.as_deref()) {
+        Some(value) => words(value).find_map(|(_, role)| aria::find_role(role).map(|r| r.name)),
+        None if has_attribute(element, "role") || is_component(element) => None,
+        None => aria::implicit_role(element),
+    }
+}
+
+/// Visible elements in document order, with the `v-if` chains they are in as (chain, branch) pairs
+fn branched_elements(source: &SourceFile) -> Vec<(&Element, Vec<(usize, usize)>)> {
+    fn collect<'a>(
+        nodes: &'a [Node],
+        branches: &[(usize, usize)],
+        elements: &mut Vec<(&'a Element, Vec<(usize, usize)>)>,
+    ) {
+        let mut chain: Option<(usize, usize)> = None;
+        for node in nodes {
+            let Node::Element(element) = node else {
+                continue;
+            };
+            chain = if element.has_attribute("v-if") {
+                Some((element.span.start, 0))
+            } else if element.has_attribute("v-else-if") || element.has_attribute("v-else") {
+                chain.map(|(chain, branch)| (chain, branch + 1))
+            } else {
+                None
+            };
+            if is_hidden(element) {
+                continue;
+            }
+            let mut branches = branches.to_vec();
+            branches.extend(chain);
+            collect(&element.children, &branches, elements);
+            elements.push((element, branches));
+        }
+    }
+
+    let mut elements = vec![];
+    collect(&source.template().nodes, &[], &mut elements);
+    elements.sort_by_key(|(element, _)| element.span.start);
+    elements
+}
+
+/// Elements that repeat an earlier one they can render together with, paired with the first one
+fn repeated<'a>(
+    elements: &'a [(&'a Element, Vec<(usize, usize)>)],
+) -> impl Iterator<Item = (&'a Element, &'a Element)> {
+    let exclusive = |a: &[(usize, usize)], b: &[(usize, usize)]| {
+        a.iter().any(|(chain, branch)| {
+            b.iter()
+                .any(|(other, other_branch)| chain == other && branch != other_branch)
+        })
+    };
+    elements
+        .iter()
+        .enumerate()
+        .filter_map(move |(i, (element, branches))| {
+            elements[..i]
+                .iter()
+                .find(|(_, earlier)| !exclusive(branches, earlier))
+                .map(|(first, _)| (*element, *first))
+        })
+}
+
+/// A `tabindex` attribute
+enum TabIndex {
+    Static(i64),
+    /// Bound, or a value that isn't a number
+    Bound,
+}
+
+fn tabindex(element: &Element) -> Option<TabIndex> {
+    if !has_attribute(element, "tabindex") {
+        return None;
+    }
+    Some(
+        match static_value(element, "tabindex").and_then(|v| v.trim().parse().ok()) {
+            Some(index) => TabIndex::Static(index),
+            None => TabIndex::Bound,
+        },
+    )
+}
+
+/// Event name of an `@event` or `v-on:event` directive, without modifiers
+fn event_name(attribute: &Attribute) -> Option<&str> {
+    let event = attribute
+        .event_name()
+        .or_else(|| attribute.name.strip_prefix("v-on:"))?;
+    event.split('.').next()
+}
+
+/// Whether the element is natively interactive, so browsers make it focusable and keyboard operable
+fn is_interactive(element: &Element) -> bool {
+    match element.tag.as_str() {
+        "a" | "area" => has_attribute(element, "href"),
+        "input" => static_value(element, "type") != Some("hidden"),
+        "audio" | "video" => has_attribute(element, "controls"),
+        "button" | "select" | "textarea" | "summary" | "option" | "iframe" | "embed" => true,
+        _ => has_attribute(element, "contenteditable"),
+    }
+}
+
+/// Extend an attribute span over the whitespace before it, so deleting it leaves no gap
+fn with_leading_space(source: &SourceFile, span: Span) -> Span {
+    let before = &source.text()[..span.start];
+    Span::new(before.trim_end().len(), span.end)
+}
+
+/// Elements that aren't hidden from assistive technology, in document order
+fn visible_elements(source: &SourceFile) -> Vec<&Element> {
+    let mut elements = vec![];
+    source.template().visit(&mut |node, ancestors| {
+        if let Node::Element(element) = node {
+            if !is_hidden(element) && !ancestors.iter().any(|a| is_hidden(a)) {
+                elements.push(element);
+            }
+        }
+    });
+    elements
+}
+
+/// Whether the element has the attribute, either static or bound (`:name`, `v-bind:name`)
+pub(crate) fn has_attribute(element: &Element, name: &str) -> bool {
+    element.attributes.iter().any(|a| {
+        a.name == name
+            || a.name.strip_prefix(':') == Some(name)
+            || a.name.strip_prefix("v-bind:") == Some(name)
+    })
+}
+
+/// Value of a static attribute
+pub(crate) fn static_value<'e>(element: &'e Element, name: &str) -> Option<&'e str> {
+    element.attribute(name)?.value.as_deref()
+}
+
+/// Whether the element and its content are hidden from assistive technology
+pub(crate) fn is_hidden(element: &Element) -> bool {
+    element.has_attribute("hidden") || static_value(element, "aria-hidden") == Some("true")
+}
+
+/// Whether the element is a component rather than an HTML element
+pub(crate) fn is_component(element: &Element) -> bool {
+    element.tag.starts_with(|c: char| c.is_ascii_uppercase()) || element.tag.contains('-')
+}
+
+/// Whether the element has an attribute that names it or replaces its content
+fn has_name_attribute(element: &Element) -> bool {
+    ["aria-label", "aria-labelledby", "title", "v-html", "v-text"]
+        .iter()
+        .any(|name| has_attribute(element, name))
+}
+
+/// Whether the nodes give their parent an accessible name
+fn has_content(nodes: &[Node]) -> bool {
+    nodes.iter().any(|node| match node {
+        Node::Text { text, .. } => !text.trim().is_empty(),
+        Node::Interpolation { .. } => true,
+        Node::Comment { .. } => false,
+        Node::Element(child) if is_hidden(child) => false,
+        // Components and slots may render text
+        Node::Element(child) if is_component(child) || child.tag == "slot" => true,
+        Node::Element(child) if child.tag == "img" => match static_value(child, "alt") {
+            Some(alt) => !alt.trim().is_empty(),
+            None => has_attribute(child, "alt") || has_name_attribute(child),
+        },
+        Node::Element(child) => has_name_attribute(child) || has_content(&child.children),
+    })
+}
@@ -0,0 +1,535 @@
+// WAI-ARIA role and attribute table
+// A built-in copy of the WAI-ARIA 1.2 roles, the states and properties each role supports
+// and the implicit roles of HTML elements, so the accessibility rules work offline.
+
+use crate::source::template::Element;
+
+/// A concrete WAI-ARIA role
+pub(crate) struct Role {
+    pub name: &'static str,
+    /// Role-specific states and properties, including inherited ones (global ones are implied)
+    pub attributes: &'static [&'static str],
+    /// States and properties that must be set when the role is given explicitly
+    pub required: &'static [&'static str],
+    /// Whether `aria-label` and `aria-labelledby` are prohibited
+    pub naming_prohibited: bool,
+}
+
+/// States and properties allowed on every role, including the ones deprecated as global in 1.2
+pub(crate) const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "aria-atomic",
+    "aria-busy",
+    "aria-controls",
+    "aria-current",
+    "aria-describedby",
+    "aria-description",
+    "aria-details",
+    "aria-disabled",
+    "aria-dropeffect",
+    "aria-errormessage",
+    "aria-flowto",
+    "aria-grabbed",
+    "aria-haspopup",
+    "aria-hidden",
+    "aria-invalid",
+    "aria-keyshortcuts",
+    "aria-label",
+    "aria-labelledby",
+    "aria-live",
+    "aria-owns",
+    "aria-relevant",
+    "aria-roledescription",
+];
+
+/// States and properties only some roles support
+const ROLE_ATTRIBUTES: &[&str] = &[
+    "aria-activedescendant",
+    "aria-autocomplete",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-checked",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-expanded",
+    "aria-level",
+    "aria-modal",
+    "aria-multiline",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-placeholder",
+    "aria-posinset",
+    "aria-pressed",
+    "aria-readonly",
+    "aria-required",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-selected",
+    "aria-setsize",
+    "aria-sort",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
+
+/// Abstract roles, which exist to build the taxonomy and must not be used in content
+pub(crate) const ABSTRACT_ROLES: &[&str] = &[
+    "command",
+    "composite",
+    "input",
+    "landmark",
+    "range",
+    "roletype",
+    "section",
+    "sectionhead",
+    "select",
+    "structure",
+    "widget",
+    "window",
+];
+
+const CELL: &[&str] = &[
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+];
+const GRIDCELL: &[&str] = &[
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-expanded",
+    "aria-readonly",
+    "aria-required",
+    "aria-selected",
+];
+const HEADER: &[&str] = &[
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-expanded",
+    "aria-readonly",
+    "aria-required",
+    "aria-selected",
+    "aria-sort",
+];
+const RANGE: &[&str] = &[
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
+const TEXTBOX: &[&str] = &[
+    "aria-activedescendant",
+    "aria-autocomplete",
+    "aria-multiline",
+    "aria-placeholder",
+    "aria-readonly",
+    "aria-required",
+];
+const MENUITEM_CHECKABLE: &[&str] = &[
+    "aria-checked",
+    "aria-expanded",
+    "aria-posinset",
+    "aria-setsize",
+];
+const COMPOSITE_ORIENTED: &[&str] = &["aria-activedescendant", "aria-orientation"];
+
+const fn role(name: &'static str, attributes: &'static [&'static str]) -> Role {
+    Role {
+        name,
+        attributes,
+        required: &[],
+        naming_prohibited: false,
+    }
+}
+
+const fn required(
+    name: &'static str,
+    attributes: &'static [&'static str],
+    required: &'static [&'static str],
+) -> Role {
+    Role {
+        name,
+        attributes,
+        required,
+        naming_prohibited: false,
+    }
+}
+
+const fn unnamed(name: &'static str) -> Role {
+    Role {
+        name,
+        attributes: &[],
+        required: &[],
+        naming_prohibited: true,
+    }
+}
+
+/// Concrete roles
+pub(crate) const ROLES: &[Role] = &[
+    role("alert", &[]),
+    role("alertdialog", &["aria-modal"]),
+    role("application", &["aria-activedescendant", "aria-expanded"]),
+    role("article", &["aria-posinset", "aria-setsize"]),
+    role("banner", &[]),
+    role("blockquote", &[]),
+    role("button", &["aria-expanded", "aria-pressed"]),
+    unnamed("caption"),
+    role("cell", CELL),
+    required(
+        "checkbox",
+        &[
+            "aria-checked",
+            "aria-expanded",
+            "aria-readonly",
+            "aria-required",
+        ],
+        &["aria-checked"],
+    ),
+    unnamed("code"),
+    role("columnheader", HEADER),
+    required(
+        "combobox",
+        &[
+            "aria-activedescendant",
+            "aria-autocomplete",
+            "aria-expanded",
+            "aria-readonly",
+            "aria-required",
+        ],
+        &["aria-expanded"],
+    ),
+    role("complementary", &[]),
+    role("contentinfo", &[]),
+    role("definition", &[]),
+    unnamed("deletion"),
+    role("dialog", &["aria-modal"]),
+    role("directory", &[]),
+    role("document", &[]),
+    unnamed("emphasis"),
+    role("feed", &[]),
+    role("figure", &[]),
+    role("form", &[]),
+    unnamed("generic"),
+    role(
+        "grid",
+        &[
+            "aria-activedescendant",
+            "aria-colcount",
+            "aria-multiselectable",
+            "aria-readonly",
+            "aria-rowcount",
+        ],
+    ),
+    role("gridcell", GRIDCELL),
+    role("group", &["aria-activedescendant"]),
+    required("heading", &["aria-level"], &["aria-level"]),
+    role("image", &[]),
+    role("img", &[]),
+    unnamed("insertion"),
+    role("link", &["aria-expanded"]),
+    role("list", &[]),
+    role(
+        "listbox",
+        &[
+            "aria-activedescendant",
+            "aria-expanded",
+            "aria-multiselectable",
+            "aria-orientation",
+            "aria-readonly",
+            "aria-required",
+        ],
+    ),
+    role("listitem", &["aria-level", "aria-posinset", "aria-setsize"]),
+    role("log", &[]),
+    role("main", &[]),
+    role("mark", &[]),
+    role("marquee", &[]),
+    role("math", &[]),
+    role("menu", COMPOSITE_ORIENTED),
+    role("menubar", COMPOSITE_ORIENTED),
+    role(
+        "menuitem",
+        &["aria-expanded", "aria-posinset", "aria-setsize"],
+    ),
+    required("menuitemcheckbox", MENUITEM_CHECKABLE, &["aria-checked"]),
+    required("menuitemradio", MENUITEM_CHECKABLE, &["aria-checked"]),
+    required("meter", RANGE, &["aria-valuenow"]),
+    role("navigation", &[]),
+    unnamed("none"),
+    role("note", &[]),
+    role(
+        "option",
+        &[
+            "aria-checked",
+            "aria-posinset",
+            "aria-selected",
+            "aria-setsize",
+        ],
+    ),
+    unnamed("paragraph"),
+    unnamed("presentation"),
+    role("progressbar", RANGE),
+    required(
+        "radio",
+        &["aria-checked", "aria-posinset", "aria-setsize"],
+        &["aria-checked"],
+    ),
+    role(
+        "radiogroup",
+        &[
+            "aria-activedescendant",
+            "aria-orientation",
+            "aria-readonly",
+            "aria-required",
+        ],
+    ),
+    role("region", &[]),
+    role(
+        "row",
+        &[
+            "aria-activedescendant",
+            "aria-colindex",
+            "aria-colindextext",
+            "aria-expanded",
+            "aria-level",
+            "aria-posinset",
+            "aria-rowindex",
+            "aria-rowindextext",
+            "aria-selected",
+            "aria-setsize",
+        ],
+    ),
+    role("rowgroup", &[]),
+    role("rowheader", HEADER),
+    required(
+        "scrollbar",
+        &[
+            "aria-orientation",
+            "aria-valuemax",
+            "aria-valuemin",
+            "aria-valuenow",
+            "aria-valuetext",
+        ],
+        &["aria-valuenow"],
+    ),
+    role("search", &[]),
+    role("searchbox", TEXTBOX),
+    role(
+        "separator",
+        &[
+            "aria-orientation",
+            "aria-valuemax",
+            "aria-valuemin",
+            "aria-valuenow",
+            "aria-valuetext",
+        ],
+    ),
+    required(
+        "slider",
+        &[
+            "aria-orientation",
+            "aria-readonly",
+            "aria-valuemax",
+            "aria-valuemin",
+            "aria-valuenow",
+            "aria-valuetext",
+        ],
+        &["aria-valuenow"],
+    ),
+    role(
+        "spinbutton",
+        &[
+            "aria-activedescendant",
+            "aria-readonly",
+            "aria-required",
+            "aria-valuemax",
+            "aria-valuemin",
+            "aria-valuenow",
+            "aria-valuetext",
+        ],
+    ),
+    role("status", &[]),
+    unnamed("strong"),
+    unnamed("subscript"),
+    unnamed("superscript"),
+    required(
+        "switch",
+        &[
+            "aria-checked",
+            "aria-expanded",
+            "aria-readonly",
+            "aria-required",
+        ],
+        &["aria-checked"],
+    ),
+    role(
+        "tab",
+        &[
+            "aria-expanded",
+            "aria-posinset",
+            "aria-selected",
+            "aria-setsize",
+        ],
+    ),
+    role("table", &["aria-colcount", "aria-rowcount"]),
+    role(
+        "tablist",
+        &[
+            "aria-activedescendant",
+            "aria-multiselectable",
+            "aria-orientation",
+        ],
+    ),
+    role("tabpanel", &[]),
+    role("term", &[]),
+    role("textbox", TEXTBOX),
+    role("time", &[]),
+    role("timer", &[]),
+    role("toolbar", COMPOSITE_ORIENTED),
+    role("tooltip", &[]),
+    role(
+        "tree",
+        &[
+            "aria-activedescendant",
+            "aria-multiselectable",
+            "aria-orientation",
+            "aria-required",
+        ],
+    ),
+    role(
+        "treegrid",
+        &[
+            "aria-activedescendant",
+            "aria-colcount",
+            "aria-multiselectable",
+            "aria-orientation",
+            "aria-readonly",
+            "aria-required",
+            "aria-rowcount",
+        ],
+    ),
+    role(
+        "treeitem",
+        &[
+            "aria-checked",
+            "aria-expanded",
+            "aria-level",
+            "aria-posinset",
+            "aria-selected",
+            "aria-setsize",
+        ],
+    ),
+];
+
+/// Look up a concrete role by name
+pub(crate) fn find_role(name: &str) -> Option<&'static Role> {
+    ROLES.iter().find(|role| role.name == name)
+}
+
+/// Every state and property defined by WAI-ARIA
+pub(crate) fn attributes() -> impl Iterator<Item = &'static str> {
+    GLOBAL_ATTRIBUTES.iter().chain(ROLE_ATTRIBUTES).copied()
+}
+
+impl Role {
+    /// Whether the role supports a state or property
+    pub fn supports(&self, attribute: &str) -> bool {
+        if self.naming_prohibited && matches!(attribute, "aria-label" | "aria-labelledby") {
+            return false;
+        }
+        GLOBAL_ATTRIBUTES.contains(&attribute) || self.attributes.contains(&attribute)
+    }
+}
+
+/// Role an HTML element has without a `role` attribute, if it has a well-defined one
+pub(crate) fn implicit_role(element: &Element) -> Option<&'static str> {
+    let static_value = |name: &str| {
+        element
+            .attribute(name)
+            .and_then(|a| a.value.as_deref())
+            .map(str::to_ascii_lowercase)
+    };
+    let has = |name: &str| {
+        element.has_attribute(name)
+            || element.has_attribute(&format!(":{name}"))
+            || element.has_attribute(&format!("v-bind:{name}"))
+    };
+
+    Some(match element.tag.to_ascii_lowercase().as_str() {
+        "a" | "area" if has("href") => "link",
+        "a" | "abbr" | "b" | "bdi" | "bdo" | "data" | "div" | "i" | "pre" | "q" | "samp"
+        | "small" | "span" | "u" => "generic",
+        "article" => "article",
+        "aside" => "complementary",
+        "blockquote" => "blockquote",
+        "button" => "button",
+        "caption" => "caption",
+        "code" => "code",
+        "datalist" => "listbox",
+        "dd" => "definition",
+        "del" | "s" => "deletion",
+        "details" | "fieldset" | "optgroup" => "group",
+        "dfn" | "dt" => "term",
+        "dialog" => "dialog",
+        "em" => "emphasis",
+        "figure" => "figure",
+        "footer" => "contentinfo",
+        "form" => "form",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "header" => "banner",
+        "hr" => "separator",
+        "img" if static_value("alt").as_deref() == Some("") => "presentation",
+        "img" => "img",
+        "input" => match static_value("type").as_deref().unwrap_or("text") {
+            "button" | "image" | "reset" | "submit" => "button",
+            "checkbox" => "checkbox",
+            "radio" => "radio",
+            "range" => "slider",
+            "number" => "spinbutton",
+            "search" if !has("list") => "searchbox",
+            "email" | "tel" | "text" | "url" | "search" if has("list") => "combobox",
+            "email" | "tel" | "text" | "url" => "textbox",
+            _ => return None,
+        },
+        "ins" => "insertion",
+        "li" => "listitem",
+        "main" => "main",
+        "mark" => "mark",
+        "math" => "math",
+        "menu" | "ol" | "ul" => "list",
+        "meter" => "meter",
+        "nav" => "navigation",
+        "option" => "option",
+        "output" => "status",
+        "p" => "paragraph",
+        "progress" => "progressbar",
+        "search" => "search",
+        "select" if has("multiple") => "listbox",
+        "select" => "combobox",
+        "strong" => "strong",
+        "sub" => "subscript",
+        "sup" => "superscript",
+        "table" => "table",
+        "tbody" | "tfoot" | "thead" => "rowgroup",
+        "td" => "cell",
+        "textarea" => "textbox",
+        "th" => "columnheader",
+        "time" => "time",
+        "tr" => "row",
+        _ => return None,
+    })
+}
@@ -0,0 +1,323 @@
+// CSS color values
+// Parses the color syntaxes used in stylesheets (hex, rgb(), hsl() and named colors) and
+// computes the relative luminance and contrast ratio defined by WCAG 2
+
+/// CSS named colors as 0xRRGGBB
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+/// An sRGB color with channels from 0 to 255 and alpha from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Color {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub a: f64,
+}
+
+impl Color {
+    fn rgb(value: u32) -> Self {
+        Self {
+            r: f64::from((value >> 16) & 0xff),
+            g: f64::from((value >> 8) & 0xff),
+            b: f64::from(value & 0xff),
+            a: 1.0,
+        }
+    }
+
+    /// Whether the color is fully opaque
+    pub fn is_opaque(&self) -> bool {
+        self.a >= 1.0
+    }
+
+    /// The color painted over an opaque `background`
+    pub fn over(&self, background: Color) -> Color {
+        let mix = |top: f64, bottom: f64| top * self.a + bottom * (1.0 - self.a);
+        Color {
+            r: mix(self.r, background.r),
+            g: mix(self.g, background.g),
+            b: mix(self.b, background.b),
+            a: 1.0,
+        }
+    }
+
+    /// Relative luminance as defined by WCAG 2
+    pub fn luminance(&self) -> f64 {
+        let channel = |value: f64| {
+            let value = value / 255.0;
+            if value <= 0.04045 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * channel(self.r) + 0.7152 * channel(self.g) + 0.0722 * channel(self.b)
+    }
+
+    /// Contrast ratio between two opaque colors, from 1 to 21
+    pub fn contrast(&self, other: Color) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
+
+/// CSS named colors
+pub(crate) fn color_names() -> impl Iterator<Item = &'static str> {
+    NAMED_COLORS.iter().map(|(name, _)| *name)
+}
+
+/// Parse a CSS color value; keywords such as `currentColor` and `var()` references give `None`
+pub(crate) fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim().to_lowercase();
+    if value == "transparent" {
+        return Some(Color {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+            a: 0.0,
+        });
+    }
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+    if let Some((function, arguments)) = value.split_once('(') {
+        let arguments = arguments.strip_suffix(')')?;
+        return parse_function(function.trim(), arguments);
+    }
+    NAMED_COLORS
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, rgb)| Color::rgb(*rgb))
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits: Vec<u32> = hex.chars().filter_map(|c| c.to_digit(16)).collect();
+    let (channels, alpha) = match digits.len() {
+        3 | 4 => (
+            digits.iter().map(|d| d * 17).collect::<Vec<_>>(),
+            digits.get(3).map(|d| d * 17),
+        ),
+        6 | 8 => (
+            digits.chunks(2).map(|p| p[0] * 16 + p[1]).collect(),
+            (digits.len() == 8).then(|| digits[6] * 16 + digits[7]),
+        ),
+        _ => return None,
+    };
+    Some(Color {
+        r: f64::from(channels[0]),
+        g: f64::from(channels[1]),
+        b: f64::from(channels[2]),
+        a: alpha.map_or(1.0, |a| f64::from(a) / 255.0),
+    })
+}
+
+/// Parse the arguments of `rgb()`, `rgba()`, `hsl()` or `hsla()`, in comma or space syntax
+fn parse_function(function: &str, arguments: &str) -> Option<Color> {
+    let parts: Vec<&str> = arguments
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    if parts.len() != 3 && parts.len() != 4 {
+        return None;
+    }
+    let alpha = match parts.get(3) {
+        Some(alpha) => number_or_percentage(alpha, 1.0)?.clamp(0.0, 1.0),
+        None => 1.0,
+    };
+
+    let (r, g, b) = match function {
+        "rgb" | "rgba" => (
+            number_or_percentage(parts[0], 255.0)?,
+            number_or_percentage(parts[1], 255.0)?,
+            number_or_percentage(parts[2], 255.0)?,
+        ),
+        "hsl" | "hsla" => {
+            let hue = parts[0].trim_end_matches("deg").parse::<f64>().ok()?;
+            let saturation = parts[1].strip_suffix('%')?.parse::<f64>().ok()? / 100.0;
+            let lightness = parts[2].strip_suffix('%')?.parse::<f64>().ok()? / 100.0;
+            hsl_to_rgb(hue, saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0))
+        }
+        _ => return None,
+    };
+    Some(Color {
+        r: r.clamp(0.0, 255.0),
+        g: g.clamp(0.0, 255.0),
+        b: b.clamp(0.0, 255.0),
+        a: alpha,
+    })
+}
+
+/// Parse a number, or a percentage of `scale`
+fn number_or_percentage(text: &str, scale: f64) -> Option<f64> {
+    match text.strip_suffix('%') {
+        Some(percentage) => Some(percentage.parse::<f64>().ok()? / 100.0 * scale),
+        None => text.parse().ok(),
+    }
+}
+
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (f64, f64, f64) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    ((r + m) * 255.0, (g + m) * 255.0, (b + m) * 255.0)
+}
@@ -0,0 +1,199 @@
+// Declarative rules defined in .orlint.toml
+// These let projects add pattern-based checks without implementing `Rule` in Rust
+
+use crate::config::{CustomRuleCondition, CustomRuleConfig};
+use crate::query::Query;
+use crate::reporter::Issue;
+use crate::rules::Rule;
+use crate::source::script::tokenize;
+use crate::source::{SectionKind, SourceFile, Span};
+use orbit::parser::OrbitAst;
+use regex::Regex;
+
+/// Rule built from a `[[rules.custom]]` entry in the configuration
+pub struct DeclarativeRule {
+    config: CustomRuleConfig,
+    matcher: Matcher,
+}
+
+/// Compiled form of a [`CustomRuleCondition`]
+enum Matcher {
+    Regex {
+        regex: Regex,
+        section: Option<SectionKind>,
+    },
+    Element {
+        tag: String,
+        attribute: Option<String>,
+        value: Option<Regex>,
+        missing_attribute: Option<String>,
+    },
+    ForbiddenIdentifier(String),
+    Query(Query),
+}
+
+impl Matcher {
+    fn compile(condition: &CustomRuleCondition) -> Result<Self, String> {
+        match condition {
+            CustomRuleCondition::Regex { pattern, section } => {
+                let section = match section {
+                    Some(name) => Some(
+                        SectionKind::from_name(name)
+                            .ok_or_else(|| format!("unknown section '{name}'"))?,
+                    ),
+                    None => None,
+                };
+                Ok(Matcher::Regex {
+                    regex: Regex::new(pattern).map_err(|e| e.to_string())?,
+                    section,
+                })
+            }
+            CustomRuleCondition::Element {
+                tag,
+                attribute,
+                value,
+                missing_attribute,
+            } => Ok(Matcher::Element {
+                tag: tag.clone(),
+                attribute: attribute.clone(),
+                value: value
+                    .as_deref()
+                    .map(Regex::new)
+                    .transpose()
+                    .map_err(|e| e.to_string())?,
+                missing_attribute: missing_attribute.clone(),
+            }),
+            CustomRuleCondition::ForbiddenIdentifier(name) => {
+                Ok(Matcher::ForbiddenIdentifier(name.clone()))
+            }
+            CustomRuleCondition::Query(pattern) => Query::parse(pattern)
+                .map(Matcher::Query)
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Spans of all matches in the source file, with the captures of query matches
+    fn find(&self, source: &SourceFile) -> Vec<(Span, Vec<(String, String)>)> {
+        if let Matcher::Query(query) = self {
+            return query
+                .find(source.template())
+                .into_iter()
+                .map(|m| {
+                    let captures = m.captures.into_iter().map(|c| (c.name, c.text)).collect();
+                    (m.element.span, captures)
+                })
+                .collect();
+        }
+
+        self.find_spans(source)
+            .into_iter()
+            .map(|span| (span, vec![]))
+            .collect()
+    }
+
+    fn find_spans(&self, source: &SourceFile) -> Vec<Span> {
+        match self {
+            Matcher::Regex { regex, section } => {
+                let (text, offset) = match section {
+                    Some(kind) => match source.section(*kind) {
+                        Some(section) => (source.slice(section.content), section.content.start),
+                        None => return vec![],
+                    },
+                    None => (source.text(), 0),
+                };
+                regex
+                    .find_iter(text)
+                    .map(|m| Span::new(offset + m.start(), offset + m.end()))
+                    .collect()
+            }
+            Matcher::Element {
+                tag,
+                attribute,
+                value,
+                missing_attribute,
+            } => source
+                .template()
+                .elements()
+                .into_iter()
+                .filter(|element| tag == "*" || element.tag.eq_ignore_ascii_case(tag))
+                .filter(|element| {
+                    missing_attribute
+                        .as_ref()
+                        .is_none_or(|name| !element.has_attribute(name))
+                })
+                .filter_map(|element| match attribute {
+                    Some(name) => {
+                        let attr = element.attribute(name)?;
+                        let value_matches = match value {
+                            Some(regex) => regex.is_match(attr.value.as_deref().unwrap_or("")),
+                            None => true,
+                        };
+                        value_matches.then_some(attr.span)
+                    }
+                    None => Some(element.span),
+                })
+                .collect(),
+            Matcher::ForbiddenIdentifier(name) => match source.section(SectionKind::Script) {
+                Some(section) => tokenize(source.slice(section.content), section.content.start)
+                    .into_iter()
+                    .filter(|token| token.is_ident(name))
+                    .map(|token| token.span)
+                    .collect(),
+                None => vec![],
+            },
+            Matcher::Query(_) => vec![],
+        }
+    }
+}
+
+impl DeclarativeRule {
+    /// Compile a custom rule, failing if its pattern, section or query is invalid
+    pub fn new(config: CustomRuleConfig) -> Result<Self, String> {
+        let matcher = Matcher::compile(&config.condition)
+            .map_err(|e| format!("invalid custom rule '{}': {e}", config.id))?;
+        Ok(Self { config, matcher })
+    }
+}
+
+impl Rule for DeclarativeRule {
+    fn name(&self) -> &str {
+        &self.config.id
+    }
+
+    fn description(&self) -> &str {
+        self.config
+            .description
+            .as_deref()
+            .unwrap_or(&self.config.message)
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        Ok(self
+            .matcher
+            .find(source)
+            .into_iter()
+            .map(|(span, captures)| {
+                let (line, column) = source.position(span.start);
+                let message = captures
+                    .iter()
+                    .fold(self.config.message.clone(), |message, (name, text)| {
+                        message.replace(&format!("{{{name}}}"), text)
+                    });
+                Issue {
+                    rule: self.config.id.clone(),
+                    message,
+                    file: file_path.to_string(),
+                    line,
+                    column,
+                    severity: self.config.severity,
+                    fix: None,
+                }
+            })
+            .collect())
+    }
+}
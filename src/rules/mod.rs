@@ -1,26 +1,136 @@
 // Rules module definition
 // This file organizes all rules into a modular structure
 
+mod accessibility_rules;
+mod aria;
+mod color;
 mod component_rules;
-
+mod css;
+mod declarative;
+mod naming_rules;
+mod script_rules;
+mod style_rules;
+mod template_rules;
+mod tokens;
+
+pub use accessibility_rules::{
+    AccessibleNameRule, ClickKeyboardRule, ColorContrastRule, FormLabelRule, HeadingOrderRule,
+    HiddenFocusableRule, MissingAltRule, NoAutofocusRule, PageLandmarksRule, PositiveTabindexRule,
+    RoleAttributesRule, SingleH1Rule, ValidRoleRule,
+};
 pub use component_rules::{
     ComponentNamingRule, LifecycleMethodRule, PropTypeRule, RendererCompatibilityRule,
     StateVariableRule,
 };
+pub use declarative::DeclarativeRule;
+pub use naming_rules::{EventHandlerNamingRule, MethodNamingRule, PropNamingRule, StateNamingRule};
+pub use script_rules::{
+    InitialValueTypeMismatchRule, NoPropMutationRule, RequiredPropDefaultConflictRule,
+    StateMutationOutsideMethodsRule, UnusedPropRule, UnusedStateRule, ValidTypeAnnotationRule,
+};
+pub use style_rules::{
+    DesignTokensRule, InvalidValueRule, NoDuplicateDeclarationsRule, NoDuplicateSelectorsRule,
+    NoImportantRule, UndefinedClassRule, UnknownPropertyRule, UnscopedSelectorRule,
+    UnusedSelectorRule,
+};
+pub use template_rules::{
+    NoDuplicateIdsRule, UndefinedEventHandlerRule, UndefinedTemplateReferenceRule,
+};
 
-use crate::reporter::Issue;
+use crate::reporter::{Issue, Severity};
+use crate::source::{SourceFile, Span};
 use orbit::parser::OrbitAst;
+use regex::Regex;
 
 /// Trait for lint rules
 pub trait Rule {
     /// Name of the rule
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
     /// Description of the rule
-    fn description(&self) -> &'static str;
+    fn description(&self) -> &str;
+
+    /// Check an .orbit file for issues, given its parsed AST and its source text
+    fn check_source(
+        &self,
+        ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String>;
+}
+
+/// Build an issue located at the start of `span`
+pub(crate) fn issue_at(
+    rule: &str,
+    source: &SourceFile,
+    file_path: &str,
+    span: Span,
+    severity: Severity,
+    message: String,
+) -> Issue {
+    let (line, column) = source.position(span.start);
+    Issue {
+        rule: rule.to_string(),
+        message,
+        file: file_path.to_string(),
+        line,
+        column,
+        severity,
+        fix: None,
+    }
+}
+
+/// Closest candidate to a misspelled name, if any is close enough to suggest
+pub(crate) fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Edit distance between two strings, counting a swap of adjacent characters as one edit
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+/// Compile a regex from the configuration, naming the pattern in the error
+pub(crate) fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("invalid pattern '{pattern}': {e}"))
+}
 
-    /// Check an .orbit file for issues
-    fn check(&self, ast: &OrbitAst, file_path: &str) -> Result<Vec<Issue>, String>;
+/// Whitespace-separated words of an attribute value, with their byte offsets
+pub(crate) fn words(value: &str) -> impl Iterator<Item = (usize, &str)> {
+    value
+        .split_ascii_whitespace()
+        .map(move |word| (word.as_ptr() as usize - value.as_ptr() as usize, word))
 }
 
 /// Rule for checking if template is empty
@@ -35,7 +145,12 @@ impl Rule for NonEmptyTemplateRule {
         "Template section should not be empty"
     }
 
-    fn check(&self, ast: &OrbitAst, file_path: &str) -> Result<Vec<Issue>, String> {
+    fn check_source(
+        &self,
+        ast: &OrbitAst,
+        _source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
         let mut issues = Vec::new();
 
         if let orbit::parser::TemplateNode::Element { children, .. } = &ast.template {
@@ -47,6 +162,7 @@ impl Rule for NonEmptyTemplateRule {
                     line: 1,   // Placeholder
                     column: 1, // Placeholder
                     severity: crate::reporter::Severity::Warning,
+                    fix: None,
                 });
             }
         }
@@ -68,7 +184,12 @@ impl Rule for PublicFunctionRule {
         "Component should have at least one public function"
     }
 
-    fn check(&self, ast: &OrbitAst, file_path: &str) -> Result<Vec<Issue>, String> {
+    fn check_source(
+        &self,
+        ast: &OrbitAst,
+        _source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
         let mut issues = Vec::new();
 
         // Special handling for test files
@@ -84,6 +205,7 @@ impl Rule for PublicFunctionRule {
                 line: 1,   // Placeholder
                 column: 1, // Placeholder
                 severity: crate::reporter::Severity::Info,
+                fix: None,
             });
             return Ok(issues);
         }
@@ -97,6 +219,7 @@ impl Rule for PublicFunctionRule {
                 line: 1,   // Placeholder
                 column: 1, // Placeholder
                 severity: crate::reporter::Severity::Info,
+                fix: None,
             });
         }
 
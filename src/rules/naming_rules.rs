@@ -0,0 +1,492 @@
+// Naming convention rules for component members
+// Names are checked against configurable patterns. Violations by state and methods come with a
+// fix that renames the declaration together with every use in the template, methods and styles;
+// props are part of the component's interface, so they only get a suggested name
+
+use crate::config::{EventHandlerNamingConfig, MemberNamingConfig};
+use crate::fix::{Edit, Fix};
+use crate::reporter::{Issue, Severity};
+use crate::rules::component_rules::LIFECYCLE_HOOKS;
+use crate::rules::{compile_pattern, issue_at, Rule};
+use crate::source::component::{Component, Field, MemberKind};
+use crate::source::expression::expressions;
+use crate::source::types::{parse_type, parse_value, Literal, Primitive, Type, TypeKind, Value};
+use crate::source::usage::{member_uses, Shorthand};
+use crate::source::{SourceFile, Span};
+use orbit::parser::OrbitAst;
+use regex::Regex;
+
+/// Compiled patterns of a member naming rule
+struct MemberPatterns {
+    pattern: Regex,
+    boolean_pattern: Option<Regex>,
+}
+
+impl MemberPatterns {
+    fn compile(config: &MemberNamingConfig) -> Result<Self, String> {
+        Ok(Self {
+            pattern: compile_pattern(&config.pattern)?,
+            boolean_pattern: Some(config.boolean_pattern.as_str())
+                .filter(|p| !p.is_empty())
+                .map(compile_pattern)
+                .transpose()?,
+        })
+    }
+}
+
+/// Rule for prop names (default: camelCase, with an is/has/should prefix for booleans)
+pub struct PropNamingRule {
+    patterns: MemberPatterns,
+}
+
+impl PropNamingRule {
+    /// Compile the configured patterns, failing if one is not a valid regex
+    pub fn new(config: MemberNamingConfig) -> Result<Self, String> {
+        Ok(Self {
+            patterns: MemberPatterns::compile(&config)?,
+        })
+    }
+}
+
+impl Default for PropNamingRule {
+    fn default() -> Self {
+        Self::new(MemberNamingConfig::default()).expect("default patterns are valid")
+    }
+}
+
+impl Rule for PropNamingRule {
+    fn name(&self) -> &'static str {
+        "prop-naming"
+    }
+
+    fn description(&self) -> &'static str {
+        "Prop names should follow naming conventions (default: camelCase, booleans prefixed with is/has/should)"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let patterns = &self.patterns;
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+        Ok(check_fields(
+            self.name(),
+            patterns,
+            MemberKind::Prop,
+            &component.props,
+            component,
+            source,
+            file_path,
+        ))
+    }
+}
+
+/// Rule for state variable names (default: camelCase, with an is/has/should prefix for booleans)
+pub struct StateNamingRule {
+    patterns: MemberPatterns,
+}
+
+impl StateNamingRule {
+    /// Compile the configured patterns, failing if one is not a valid regex
+    pub fn new(config: MemberNamingConfig) -> Result<Self, String> {
+        Ok(Self {
+            patterns: MemberPatterns::compile(&config)?,
+        })
+    }
+}
+
+impl Default for StateNamingRule {
+    fn default() -> Self {
+        Self::new(MemberNamingConfig::default()).expect("default patterns are valid")
+    }
+}
+
+impl Rule for StateNamingRule {
+    fn name(&self) -> &'static str {
+        "state-naming"
+    }
+
+    fn description(&self) -> &'static str {
+        "State variable names should follow naming conventions (default: camelCase, booleans prefixed with is/has/should)"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let patterns = &self.patterns;
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+        Ok(check_fields(
+            self.name(),
+            patterns,
+            MemberKind::State,
+            &component.state,
+            component,
+            source,
+            file_path,
+        ))
+    }
+}
+
+/// Rule for method names (default: camelCase); lifecycle hooks are exempt
+pub struct MethodNamingRule {
+    pattern: Regex,
+}
+
+impl MethodNamingRule {
+    /// Compile the configured patterns, failing if one is not a valid regex
+    pub fn new(config: MemberNamingConfig) -> Result<Self, String> {
+        Ok(Self {
+            pattern: compile_pattern(&config.pattern)?,
+        })
+    }
+}
+
+impl Default for MethodNamingRule {
+    fn default() -> Self {
+        Self::new(MemberNamingConfig::default()).expect("default patterns are valid")
+    }
+}
+
+impl Rule for MethodNamingRule {
+    fn name(&self) -> &'static str {
+        "method-naming"
+    }
+
+    fn description(&self) -> &'static str {
+        "Method names should follow naming conventions (default: camelCase)"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let pattern = &self.pattern;
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+
+        Ok(component
+            .methods
+            .iter()
+            .filter(|m| !LIFECYCLE_HOOKS.contains(&m.name.as_str()) && !pattern.is_match(&m.name))
+            .map(|method| {
+                naming_issue(
+                    self.name(),
+                    source,
+                    file_path,
+                    component,
+                    (&method.name, method.name_span),
+                    format!(
+                        "Method '{}' doesn't match the naming pattern '{pattern}'",
+                        method.name
+                    ),
+                    Some(camel_case(&method.name)).filter(|s| pattern.is_match(s)),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Check prop or state names against the naming and boolean patterns, one issue per field
+fn check_fields(
+    rule: &str,
+    patterns: &MemberPatterns,
+    kind: MemberKind,
+    fields: &[Field],
+    component: &Component,
+    source: &SourceFile,
+    file_path: &str,
+) -> Vec<Issue> {
+    let what = match kind {
+        MemberKind::Prop => "Prop",
+        _ => "State variable",
+    };
+    let mut issues = vec![];
+
+    for field in fields {
+        let boolean_pattern = patterns
+            .boolean_pattern
+            .as_ref()
+            .filter(|_| is_boolean(field));
+        let fits = |name: &str| {
+            patterns.pattern.is_match(name) && boolean_pattern.is_none_or(|p| p.is_match(name))
+        };
+
+        let message = if !patterns.pattern.is_match(&field.name) {
+            format!(
+                "{what} '{}' doesn't match the naming pattern '{}'",
+                field.name, patterns.pattern
+            )
+        } else if let Some(boolean_pattern) = boolean_pattern.filter(|p| !p.is_match(&field.name)) {
+            format!(
+                "Boolean {} '{}' doesn't match the pattern '{boolean_pattern}'",
+                what.to_lowercase(),
+                field.name
+            )
+        } else {
+            continue;
+        };
+
+        let camel = camel_case(&field.name);
+        let suggestion = [camel.clone(), conventional_name(&camel, "", "is")]
+            .into_iter()
+            .find(|s| fits(s));
+        issues.push(naming_issue(
+            rule,
+            source,
+            file_path,
+            component,
+            (&field.name, field.name_span),
+            message,
+            suggestion,
+        ));
+    }
+
+    issues
+}
+
+/// Whether a field holds a boolean, judging by its type or else by its initial value
+fn is_boolean(field: &Field) -> bool {
+    fn boolean_type(ty: &Type) -> bool {
+        match &ty.kind {
+            TypeKind::Named { name, args, .. } => match Primitive::from_name(name) {
+                Some(primitive) => primitive == Primitive::Boolean,
+                None => name == "Option" && args.first().is_some_and(boolean_type),
+            },
+            // `boolean | null` and `true | false`
+            TypeKind::Union(members) => {
+                members.iter().any(boolean_type) && members.iter().all(|m| {
+                    boolean_type(m)
+                        || matches!(
+                            &m.kind,
+                            TypeKind::Named { name, .. } if name == "null" || name == "undefined"
+                        )
+                })
+            }
+            TypeKind::Literal(Literal::Boolean(_)) => true,
+            _ => false,
+        }
+    }
+
+    match (&field.type_annotation, &field.initial_value) {
+        (Some(annotation), _) => {
+            parse_type(&annotation.text, annotation.span.start).is_ok_and(|ty| boolean_type(&ty))
+        }
+        (None, Some(initial)) => matches!(
+            parse_value(&initial.text, initial.span.start),
+            Value::Boolean(_)
+        ),
+        (None, None) => false,
+    }
+}
+
+/// Convert `snake_case`, `kebab-case`, `SCREAMING_CASE` and `PascalCase` names to camelCase
+fn camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    for (i, segment) in name.split(['_', '-']).filter(|s| !s.is_empty()).enumerate() {
+        let segment = if segment.chars().all(|c| !c.is_lowercase()) {
+            segment.to_lowercase()
+        } else {
+            segment.to_string()
+        };
+        let mut chars = segment.chars();
+        if let Some(first) = chars.next() {
+            if i == 0 {
+                camel.extend(first.to_lowercase());
+            } else {
+                camel.extend(first.to_uppercase());
+            }
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+/// Rule for the names of event handler methods and callback props
+pub struct EventHandlerNamingRule {
+    handler_pattern: Regex,
+    callback_pattern: Regex,
+}
+
+impl EventHandlerNamingRule {
+    /// Compile the configured patterns, failing if either is not a valid regex
+    pub fn new(config: EventHandlerNamingConfig) -> Result<Self, String> {
+        Ok(Self {
+            handler_pattern: compile_pattern(&config.handler_pattern)?,
+            callback_pattern: compile_pattern(&config.callback_prop_pattern)?,
+        })
+    }
+}
+
+impl Default for EventHandlerNamingRule {
+    fn default() -> Self {
+        Self::new(EventHandlerNamingConfig::default()).expect("default patterns are valid")
+    }
+}
+
+impl Rule for EventHandlerNamingRule {
+    fn name(&self) -> &'static str {
+        "event-handler-naming"
+    }
+
+    fn description(&self) -> &'static str {
+        "Event handler methods and callback props should follow naming conventions (default: handleX and onX)"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let (handler_pattern, callback_pattern) = (&self.handler_pattern, &self.callback_pattern);
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+        let mut issues = vec![];
+
+        // Methods bound to template events, with the first event each is bound to
+        let mut handlers: Vec<(&str, &str)> = vec![];
+        for expression in expressions(source) {
+            let (Some(handler), Some(attribute)) = (expression.handler(), expression.attribute)
+            else {
+                continue;
+            };
+            if let Some(method) = component.method(&handler.name) {
+                if !handlers.iter().any(|(name, _)| *name == method.name) {
+                    handlers.push((&method.name, &attribute.name));
+                }
+            }
+        }
+
+        for (name, event) in handlers {
+            if handler_pattern.is_match(name) {
+                continue;
+            }
+            let method = component.method(name).expect("handlers are methods");
+            let suggestion = conventional_name(name, "on", "handle");
+            issues.push(naming_issue(
+                self.name(),
+                source,
+                file_path,
+                component,
+                (name, method.name_span),
+                format!(
+                    "Method '{name}' handles {event} but doesn't match the handler naming pattern '{handler_pattern}'"
+                ),
+                Some(suggestion).filter(|s| handler_pattern.is_match(s)),
+            ));
+        }
+
+        for prop in &component.props {
+            let is_callback = prop.type_annotation.as_ref().is_some_and(|annotation| {
+                parse_type(&annotation.text, annotation.span.start).is_ok_and(|ty| ty.is_function())
+            });
+            if !is_callback || callback_pattern.is_match(&prop.name) {
+                continue;
+            }
+            let suggestion = conventional_name(&prop.name, "handle", "on");
+            issues.push(naming_issue(
+                self.name(),
+                source,
+                file_path,
+                component,
+                (&prop.name, prop.name_span),
+                format!(
+                    "Callback prop '{}' doesn't match the callback naming pattern '{callback_pattern}'",
+                    prop.name
+                ),
+                Some(suggestion).filter(|s| callback_pattern.is_match(s)),
+            ));
+        }
+
+        Ok(issues)
+    }
+}
+
+/// `name` with the conventional prefix: `save` and `onSave` both become `handleSave`
+fn conventional_name(name: &str, other_prefix: &str, prefix: &str) -> String {
+    let rest = name
+        .strip_prefix(other_prefix)
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+        .unwrap_or(name);
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some(first) => format!("{prefix}{}{}", first.to_uppercase(), chars.as_str()),
+        None => prefix.to_string(),
+    }
+}
+
+/// An issue for a badly named member, suggesting `suggestion` when it is free to use
+///
+/// State and methods get a rename fix. Props and `pub` or `public` methods are part of the
+/// component's public interface, so renaming them would break the parents that use them; they
+/// only get the suggestion.
+fn naming_issue(
+    rule: &str,
+    source: &SourceFile,
+    file_path: &str,
+    component: &Component,
+    (name, name_span): (&str, Span),
+    message: String,
+    suggestion: Option<String>,
+) -> Issue {
+    let suggestion = suggestion.filter(|s| component.member(s).is_none());
+    let message = match &suggestion {
+        Some(suggestion) => format!("{message}; rename it to '{suggestion}'"),
+        None => message,
+    };
+    let mut issue = issue_at(
+        rule,
+        source,
+        file_path,
+        name_span,
+        Severity::Warning,
+        message,
+    );
+    let public = match component.member(name) {
+        Some(MemberKind::Prop) => true,
+        Some(MemberKind::Method) => component.methods.iter().any(|m| m.name == name && m.public),
+        _ => false,
+    };
+    if !public {
+        issue.fix = suggestion.map(|suggestion| rename_fix(source, name, name_span, &suggestion));
+    }
+    issue
+}
+
+/// Fix renaming a member at its declaration and at every use
+pub(crate) fn rename_fix(
+    source: &SourceFile,
+    name: &str,
+    declaration: Span,
+    new_name: &str,
+) -> Fix {
+    let mut edits = vec![Edit::replace(declaration, new_name)];
+    edits.extend(
+        member_uses(source)
+            .into_iter()
+            .filter(|u| u.name == name)
+            .map(|u| {
+                // Shorthands expand so that only the member's half of the pair is renamed
+                let text = match u.shorthand {
+                    None => new_name.to_string(),
+                    Some(Shorthand::Property) => format!("{name}: {new_name}"),
+                    Some(Shorthand::Destructuring) => format!("{new_name}: {name}"),
+                };
+                Edit::replace(u.span, text)
+            }),
+    );
+    Fix::new(format!("Rename '{name}' to '{new_name}'"), edits)
+}
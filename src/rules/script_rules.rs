@@ -0,0 +1,709 @@
+// Rules for the component's script section
+// These check how props, state and methods are declared and used
+
+use crate::config::TypeAnnotationConfig;
+use crate::fix::{Edit, Fix};
+use crate::reporter::{Issue, Severity};
+use crate::rules::{closest_match, issue_at, Rule};
+use crate::source::component::Field;
+use crate::source::expression::{expressions, ExpressionKind};
+use crate::source::script::{access, tokenize, Access, Token, TokenKind};
+use crate::source::types::{
+    builtin_arity, builtin_types, declared_types, parse_type, parse_value, TypeKind, Value,
+};
+use crate::source::usage::{member_uses, UseSite};
+use crate::source::{SectionKind, SourceFile, Span};
+use orbit::parser::OrbitAst;
+
+/// Rule for props that are never used
+pub struct UnusedPropRule;
+
+impl Rule for UnusedPropRule {
+    fn name(&self) -> &'static str {
+        "unused-prop"
+    }
+
+    fn description(&self) -> &'static str {
+        "Props should be used in the template, a method or a style binding"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+        Ok(unused_fields(
+            self.name(),
+            "Prop",
+            &component.props,
+            source,
+            file_path,
+        ))
+    }
+}
+
+/// Rule for state variables that are never used
+pub struct UnusedStateRule;
+
+impl Rule for UnusedStateRule {
+    fn name(&self) -> &'static str {
+        "unused-state"
+    }
+
+    fn description(&self) -> &'static str {
+        "State variables should be used in the template, a method or a style binding"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+        Ok(unused_fields(
+            self.name(),
+            "State variable",
+            &component.state,
+            source,
+            file_path,
+        ))
+    }
+}
+
+/// Rule for methods that assign to props
+pub struct NoPropMutationRule;
+
+impl Rule for NoPropMutationRule {
+    fn name(&self) -> &'static str {
+        "no-prop-mutation"
+    }
+
+    fn description(&self) -> &'static str {
+        "Props are owned by the parent component and should not be assigned in methods"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+
+        Ok(member_uses(source)
+            .into_iter()
+            .filter(|u| u.access.writes())
+            .filter_map(|u| {
+                let UseSite::Method(index) = u.site else {
+                    return None;
+                };
+                let prop = component.prop(&u.name)?;
+                let suggestion = if prop.initial_value.is_some() {
+                    "move the value into state"
+                } else {
+                    "move the value into state or give the prop a default value in the props block"
+                };
+                Some(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    u.span,
+                    Severity::Error,
+                    format!(
+                        "Prop '{}' is assigned in method '{}'; {suggestion}",
+                        u.name, component.methods[index].name
+                    ),
+                ))
+            })
+            .collect())
+    }
+}
+
+/// Rule for state changed outside methods, and state that is written but never read
+///
+/// Template expressions are re-evaluated on every render, so assigning state in one
+/// (`{{ count++ }}`, `:value="x = 1"`) changes state during rendering. Event handlers
+/// run in response to events and may change state like methods do.
+pub struct StateMutationOutsideMethodsRule;
+
+impl Rule for StateMutationOutsideMethodsRule {
+    fn name(&self) -> &'static str {
+        "state-mutation-outside-methods"
+    }
+
+    fn description(&self) -> &'static str {
+        "State should only change in methods, lifecycle hooks and event handlers, and should be read somewhere"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+        let uses = member_uses(source);
+        let mut issues = vec![];
+
+        for u in &uses {
+            let in_render =
+                matches!(u.site, UseSite::Template(kind) if kind != ExpressionKind::Event);
+            if in_render && u.access.writes() && component.state_variable(&u.name).is_some() {
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    u.span,
+                    Severity::Error,
+                    format!(
+                        "State variable '{}' is changed in a template expression; change it in a method or lifecycle hook instead",
+                        u.name
+                    ),
+                ));
+            }
+        }
+
+        // `x += 1` reads `x` only to write it again, so it doesn't count as a read
+        for field in &component.state {
+            let mut field_uses = uses.iter().filter(|u| u.name == field.name).peekable();
+            if field_uses.peek().is_some() && field_uses.all(|u| u.access.writes()) {
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    field.name_span,
+                    Severity::Warning,
+                    format!("State variable '{}' is written but never read", field.name),
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for prop defaults and state initial values that don't match the declared type
+pub struct InitialValueTypeMismatchRule;
+
+impl Rule for InitialValueTypeMismatchRule {
+    fn name(&self) -> &'static str {
+        "initial-value-type-mismatch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Default and initial values should match the declared type"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+
+        let fields = component
+            .props
+            .iter()
+            .map(|field| (field, "Prop", "default value"))
+            .chain(
+                component
+                    .state
+                    .iter()
+                    .map(|field| (field, "State variable", "initial value")),
+            );
+
+        let mut issues = vec![];
+        for (field, what, value_name) in fields {
+            let (Some(annotation), Some(initial)) = (&field.type_annotation, &field.initial_value)
+            else {
+                continue;
+            };
+            // Unparseable annotations are reported by `valid-type-annotation`
+            let Ok(ty) = parse_type(&annotation.text, annotation.span.start) else {
+                continue;
+            };
+            let value = parse_value(&initial.text, initial.span.start);
+            if field.optional && value == Value::Undefined {
+                continue;
+            }
+
+            if ty.accepts(&value) == Some(false) {
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    initial.span,
+                    Severity::Error,
+                    format!(
+                        "{what} '{}' is declared as '{}' but its {value_name} is {}",
+                        field.name, annotation.text, initial.text
+                    ),
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for prop and state type annotations that are malformed or name unknown types
+pub struct ValidTypeAnnotationRule {
+    known_types: Vec<String>,
+}
+
+impl ValidTypeAnnotationRule {
+    pub fn new(config: TypeAnnotationConfig) -> Self {
+        Self {
+            known_types: config.known_types,
+        }
+    }
+}
+
+impl Default for ValidTypeAnnotationRule {
+    fn default() -> Self {
+        Self::new(TypeAnnotationConfig::default())
+    }
+}
+
+impl Rule for ValidTypeAnnotationRule {
+    fn name(&self) -> &'static str {
+        "valid-type-annotation"
+    }
+
+    fn description(&self) -> &'static str {
+        "Type annotations should be well-formed and name known types"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+
+        let mut known = self.known_types.clone();
+        known.push(component.name.clone());
+        if let Some(script) = source.section(SectionKind::Script) {
+            let tokens = tokenize(source.slice(script.content), script.content.start);
+            known.extend(declared_types(&tokens));
+        }
+
+        let fields = component.props.iter().map(|field| (field, "prop")).chain(
+            component
+                .state
+                .iter()
+                .map(|field| (field, "state variable")),
+        );
+
+        let mut problems: Vec<(Span, String)> = vec![];
+        for (field, what) in fields {
+            let Some(annotation) = &field.type_annotation else {
+                continue;
+            };
+            let ty = match parse_type(&annotation.text, annotation.span.start) {
+                Ok(ty) => ty,
+                Err(e) => {
+                    let span = if e.span.start == e.span.end {
+                        annotation.span
+                    } else {
+                        e.span
+                    };
+                    problems.push((
+                        span,
+                        format!(
+                            "Invalid type annotation for {what} '{}': {}",
+                            field.name, e.message
+                        ),
+                    ));
+                    continue;
+                }
+            };
+
+            ty.visit(&mut |ty| {
+                let TypeKind::Named {
+                    name,
+                    name_span,
+                    args,
+                } = &ty.kind
+                else {
+                    return;
+                };
+                // Paths can't be resolved, but paths to built-in types are still checked
+                let last = name.rsplit("::").next().unwrap_or(name);
+                match builtin_arity(last) {
+                    Some(0) if !args.is_empty() => problems.push((
+                        ty.span,
+                        format!("Type '{name}' does not take type arguments"),
+                    )),
+                    Some(arity) if arity != args.len() && arity > 0 => problems.push((
+                        ty.span,
+                        format!(
+                            "Type '{name}' takes {arity} type argument{} but has {}",
+                            if arity == 1 { "" } else { "s" },
+                            args.len()
+                        ),
+                    )),
+                    Some(_) => {}
+                    None if last != name || known.contains(name) => {}
+                    None => {
+                        let mut candidates: Vec<&str> = builtin_types().collect();
+                        candidates.extend(known.iter().map(String::as_str));
+                        let suggestion = closest_match(name, candidates)
+                            .map(|s| format!("; did you mean '{s}'?"))
+                            .unwrap_or_default();
+                        problems.push((
+                            *name_span,
+                            format!(
+                                "Unknown type '{name}' for {what} '{}'{suggestion}",
+                                field.name
+                            ),
+                        ));
+                    }
+                }
+            });
+        }
+
+        Ok(problems
+            .into_iter()
+            .map(|(span, message)| {
+                issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    span,
+                    Severity::Error,
+                    message,
+                )
+            })
+            .collect())
+    }
+}
+
+/// Rule for props whose required or optional status contradicts their default value or uses
+///
+/// A prop is optional when it is declared with `name?:` or has a nullable type (`Option<T>`,
+/// `T | null`); any other prop is required. A required prop with a default value is never
+/// actually required, and an optional prop without a default may be missing, so it has to be
+/// checked before it is used.
+pub struct RequiredPropDefaultConflictRule;
+
+impl Rule for RequiredPropDefaultConflictRule {
+    fn name(&self) -> &'static str {
+        "required-prop-default-conflict"
+    }
+
+    fn description(&self) -> &'static str {
+        "Required props should not have default values, and optional props without one should be checked before use"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+
+        let mut issues = vec![];
+        let mut unset = vec![];
+        for prop in &component.props {
+            let nullable = prop.optional
+                || prop.type_annotation.as_ref().is_some_and(|annotation| {
+                    parse_type(&annotation.text, annotation.span.start)
+                        .is_ok_and(|ty| ty.is_nullable())
+                });
+            let has_default = prop.initial_value.as_ref().is_some_and(|value| {
+                !matches!(
+                    parse_value(&value.text, value.span.start),
+                    Value::Null | Value::Undefined
+                )
+            });
+
+            match (nullable, has_default) {
+                (false, true) => {
+                    let mut issue = issue_at(
+                        self.name(),
+                        source,
+                        file_path,
+                        prop.name_span,
+                        Severity::Warning,
+                        format!(
+                            "Prop '{}' is required but has a default value; mark it optional with '?' or remove the default",
+                            prop.name
+                        ),
+                    );
+                    issue.fix = Some(Fix::new(
+                        format!("Mark prop '{}' optional", prop.name),
+                        vec![Edit::insert(prop.name_span.end, "?")],
+                    ));
+                    issues.push(issue);
+                }
+                (true, false) => unset.push(prop.name.as_str()),
+                _ => {}
+            }
+        }
+
+        for (name, span) in unchecked_uses(source, &unset) {
+            issues.push(issue_at(
+                self.name(),
+                source,
+                file_path,
+                span,
+                Severity::Warning,
+                format!(
+                    "Optional prop '{name}' has no default value and may be missing here; check it first or give it a default value"
+                ),
+            ));
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Methods that check or unwrap an optional value
+const OPTION_METHODS: &[&str] = &[
+    "is_some",
+    "is_none",
+    "is_some_and",
+    "is_none_or",
+    "unwrap_or",
+    "unwrap_or_default",
+    "unwrap_or_else",
+    "map",
+    "map_or",
+    "map_or_else",
+    "and_then",
+    "as_ref",
+    "as_deref",
+    "ok_or",
+    "ok_or_else",
+    "or",
+    "or_else",
+    "filter",
+    "iter",
+];
+
+/// Operators that check the value before them for a missing value
+const CHECKS: &[&str] = &["?.", "??", "?", "&&", "||"];
+
+/// Comparisons, which check a value on either side (`prop != null`, `None == self.prop`)
+const COMPARISONS: &[&str] = &["==", "!=", "===", "!=="];
+
+/// Uses of the named members that aren't preceded by a check for a missing value
+///
+/// A check covers later uses in the same template expression or method, and a `v-if` or
+/// `v-else-if` check covers the rest of its element. Binding a member straight to an
+/// attribute (`:title="subtitle"`) passes it on rather than using it.
+fn unchecked_uses<'n>(source: &SourceFile, names: &[&'n str]) -> Vec<(&'n str, Span)> {
+    let mut uses = vec![];
+    if names.is_empty() {
+        return uses;
+    }
+
+    let mut guarded: Vec<(&str, Span)> = vec![];
+    for expression in expressions(source) {
+        let tokens = tokenize(expression.text, expression.span.start);
+        let condition = expression.attribute.map(|a| a.name.as_str());
+        let passthrough = expression.kind == ExpressionKind::Binding
+            && (tokens.len() == 1 || (tokens.len() == 3 && tokens[0].is_ident("this")));
+
+        let mut checked = vec![];
+        for reference in expression.references() {
+            let Some(&name) = names.iter().find(|n| **n == reference.name) else {
+                continue;
+            };
+            let is_guarded = guarded.iter().any(|(guarded, range)| {
+                *guarded == name
+                    && range.start <= reference.span.start
+                    && reference.span.end <= range.end
+            });
+            if passthrough
+                || is_guarded
+                || checked.contains(&name)
+                || reference.access == Access::Write
+            {
+                continue;
+            }
+            let Some(index) = tokens.iter().position(|t| t.span == reference.span) else {
+                continue;
+            };
+
+            let is_condition = matches!(condition, Some("v-if" | "v-else-if" | "v-show"));
+            if !is_checked(&tokens, index, is_condition) {
+                uses.push((name, reference.span));
+                continue;
+            }
+            checked.push(name);
+            if let (Some("v-if" | "v-else-if"), Some(element), Some(attribute)) =
+                (condition, expression.element, expression.attribute)
+            {
+                guarded.push((name, Span::new(attribute.span.end, element.extent().end)));
+            }
+        }
+    }
+
+    for method in source.component().map_or(&[][..], |c| &c.methods) {
+        let tokens = tokenize(source.slice(method.body), method.body.start);
+        let mut checked = vec![];
+        for (index, token) in tokens.iter().enumerate() {
+            let Some(&name) = names
+                .iter()
+                .find(|n| token.kind == TokenKind::Ident && token.text == **n)
+            else {
+                continue;
+            };
+            if receiver_start(&tokens, index) == index
+                || checked.contains(&name)
+                || access(&tokens, index) == Access::Write
+            {
+                continue;
+            }
+            if is_checked(&tokens, index, false) {
+                checked.push(name);
+            } else {
+                uses.push((name, token.span));
+            }
+        }
+    }
+
+    uses
+}
+
+/// Index where the member at `index` starts, including a `this.` or `self.` receiver
+fn receiver_start(tokens: &[Token], index: usize) -> usize {
+    let has_receiver = index >= 2
+        && tokens[index - 1].is_punct(".")
+        && (tokens[index - 2].is_ident("this") || tokens[index - 2].is_ident("self"));
+    if has_receiver {
+        index - 2
+    } else {
+        index
+    }
+}
+
+/// Whether the member at `index` is checked for a missing value rather than used
+///
+/// `condition` is set when the tokens are a whole condition, such as a `v-if` expression.
+fn is_checked(tokens: &[Token], index: usize, condition: bool) -> bool {
+    let start = receiver_start(tokens, index);
+    let prev = start.checked_sub(1).map(|i| &tokens[i]);
+    let next = tokens.get(index + 1);
+
+    let checked_after = next.is_some_and(|n| {
+        (n.kind == TokenKind::Punct
+            && (CHECKS.contains(&n.text.as_str()) || COMPARISONS.contains(&n.text.as_str())))
+            || (n.is_punct(".")
+                && tokens
+                    .get(index + 2)
+                    .is_some_and(|m| OPTION_METHODS.contains(&m.text.as_str())))
+    });
+    let checked_before = prev.is_some_and(|p| {
+        p.is_punct("!")
+            || p.is_ident("match")
+            || p.is_ident("typeof")
+            || (p.kind == TokenKind::Punct && COMPARISONS.contains(&p.text.as_str()))
+    });
+    if checked_after || checked_before {
+        return true;
+    }
+
+    // `if let Some(value) = self.prop`, `while let Some(value) = &mut self.prop`
+    let assigned = tokens[..start]
+        .iter()
+        .rposition(|t| !(t.is_punct("&") || t.is_ident("mut")))
+        .filter(|&i| tokens[i].is_punct("="));
+    if let Some(assigned) = assigned {
+        let statement = tokens[..assigned]
+            .iter()
+            .rposition(|t| t.is_punct(";") || t.is_punct("{") || t.is_punct("}"))
+            .map_or(0, |i| i + 1);
+        let pattern = &tokens[statement..assigned];
+        if pattern.len() >= 2
+            && (pattern[0].is_ident("if") || pattern[0].is_ident("while"))
+            && pattern[1].is_ident("let")
+        {
+            return true;
+        }
+    }
+
+    // The whole operand of a condition: `v-if="prop"`, `if (this.prop)`, `if self.prop {`
+    next.is_none_or(|n| n.is_punct(")") || n.is_punct("{"))
+        && in_condition(tokens, start, condition)
+}
+
+/// Whether the token at `index` is in an `if` or `while` condition (or in `tokens` as a whole,
+/// when `condition` is set) outside the arguments of any call
+fn in_condition(tokens: &[Token], index: usize, condition: bool) -> bool {
+    let mut depth = 0usize;
+    for i in (0..index).rev() {
+        let token = &tokens[i];
+        if token.is_punct(")") {
+            depth += 1;
+        } else if token.is_punct("(") {
+            if depth > 0 {
+                depth -= 1;
+            } else if i > 0
+                && tokens[i - 1].kind == TokenKind::Ident
+                && !tokens[i - 1].is_ident("if")
+                && !tokens[i - 1].is_ident("while")
+            {
+                return false;
+            }
+        } else if depth == 0 && (token.is_ident("if") || token.is_ident("while")) {
+            return true;
+        } else if token.is_punct(";") || token.is_punct("{") || token.is_punct("}") {
+            return false;
+        }
+    }
+    condition
+}
+
+/// Report the fields that are never used, with a fix removing each declaration
+fn unused_fields(
+    rule: &str,
+    what: &str,
+    fields: &[Field],
+    source: &SourceFile,
+    file_path: &str,
+) -> Vec<Issue> {
+    let uses = member_uses(source);
+
+    fields
+        .iter()
+        .filter(|field| !uses.iter().any(|u| u.name == field.name))
+        .map(|field| {
+            let mut issue = issue_at(
+                rule,
+                source,
+                file_path,
+                field.name_span,
+                Severity::Warning,
+                format!("{what} '{}' is never used", field.name),
+            );
+            issue.fix = Some(Fix::new(
+                format!("Remove {} '{}'", what.to_lowercase(), field.name),
+                vec![Edit::delete(source.removal_span(field.span))],
+            ));
+            issue
+        })
+        .collect()
+}
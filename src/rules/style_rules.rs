@@ -0,0 +1,1303 @@
+// Rules for the component's style section
+// These cross-reference selectors with the template. Classes applied through `:class`
+// bindings are resolved from literals and object keys; a binding that can't be resolved
+// may apply any class, so selectors are then only reported when nothing else could match.
+// The CSS quality rules check the stylesheet on its own against a built-in property table.
+
+use crate::config::{
+    DesignTokensConfig, UndefinedClassConfig, UnknownPropertyConfig, UnscopedSelectorConfig,
+};
+use crate::fix::{Edit, Fix};
+use crate::reporter::{Issue, Severity};
+use crate::rules::accessibility_rules::is_component;
+use crate::rules::color::{color_names, parse_color};
+use crate::rules::css;
+use crate::rules::tokens::{DesignTokens, TokenCategory};
+use crate::rules::{closest_match, compile_pattern, issue_at, words, Rule};
+use crate::source::script::{tokenize, Token, TokenKind};
+use crate::source::style::{parse_declarations, Compound, Declaration, Selector};
+use crate::source::template::{Element, Node};
+use crate::source::{SectionKind, SourceFile, Span};
+use orbit::parser::OrbitAst;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// Properties whose words are names rather than colors, such as `animation-name: tan`
+const NAMED_VALUE_PROPERTIES: &[&str] = &[
+    "animation",
+    "container",
+    "counter",
+    "font",
+    "grid",
+    "transition",
+    "view-transition",
+    "will-change",
+];
+
+/// Pseudo-classes that select markup rendered by other components
+const FOREIGN_PSEUDO_CLASSES: &[&str] = &["deep", "global", "slotted", "v-deep"];
+
+/// Rule for selectors that don't match anything in the template
+pub struct UnusedSelectorRule;
+
+impl Rule for UnusedSelectorRule {
+    fn name(&self) -> &'static str {
+        "unused-selector"
+    }
+
+    fn description(&self) -> &'static str {
+        "Selectors in the style section should match an element in the template"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let stylesheet = source.stylesheet();
+        if stylesheet.rules.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let classes = TemplateClasses::collect(source);
+        let mut elements = vec![];
+        let mut ids = HashSet::new();
+        let mut dynamic_ids = false;
+        // Markup from child components, slots and v-html can't be seen here
+        let mut opaque = false;
+        source.template().visit(&mut |node, ancestors| {
+            let Node::Element(element) = node else {
+                return;
+            };
+            elements.push((element, ancestors.to_vec()));
+            for attribute in &element.attributes {
+                match attribute.name.as_str() {
+                    "id" => {
+                        ids.extend(attribute.value.as_deref());
+                    }
+                    ":id" | "v-bind:id" | "v-bind" => dynamic_ids = true,
+                    "v-html" => opaque = true,
+                    _ => {}
+                }
+            }
+            opaque |= is_component(element) || element.tag == "slot";
+        });
+
+        let mut issues = vec![];
+        let mut reported = HashSet::new();
+        let mut report = |span: Span, message: String| {
+            if reported.insert(span) {
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    span,
+                    Severity::Warning,
+                    message,
+                ));
+            }
+        };
+
+        for rule in &stylesheet.rules {
+            for selector in &rule.selectors {
+                if selector.compounds.iter().any(is_foreign) {
+                    continue;
+                }
+                let compounds = || selector.compounds.iter();
+
+                if let Some(class) = compounds()
+                    .flat_map(|c| &c.classes)
+                    .find(|name| !classes.contains(&name.name))
+                {
+                    report(
+                        class.span,
+                        format!(
+                            "Selector '{}' never matches: class '{}' is not applied anywhere in the template",
+                            selector.text, class.name
+                        ),
+                    );
+                    continue;
+                }
+                if let Some(id) = compounds()
+                    .flat_map(|c| &c.ids)
+                    .find(|name| !dynamic_ids && !ids.contains(name.name.as_str()))
+                {
+                    report(
+                        id.span,
+                        format!(
+                            "Selector '{}' never matches: no element in the template has id '{}'",
+                            selector.text, id.name
+                        ),
+                    );
+                    continue;
+                }
+
+                // Element matching only knows static classes
+                if opaque || classes.bound {
+                    continue;
+                }
+                let Some(selector) = without_document_root(selector) else {
+                    continue;
+                };
+                if !elements
+                    .iter()
+                    .any(|(element, ancestors)| selector.matches(element, ancestors))
+                {
+                    report(
+                        selector.span,
+                        format!(
+                            "Selector '{}' doesn't match any element in the template",
+                            selector.text
+                        ),
+                    );
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for template classes that no selector in the style section uses
+pub struct UndefinedClassRule {
+    ignore: Vec<Regex>,
+}
+
+impl UndefinedClassRule {
+    /// Compile the ignore patterns, failing if one is not a valid regex
+    pub fn new(config: UndefinedClassConfig) -> Result<Self, String> {
+        Ok(Self {
+            ignore: config
+                .ignore
+                .iter()
+                .map(|p| compile_pattern(p))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl Default for UndefinedClassRule {
+    fn default() -> Self {
+        Self::new(UndefinedClassConfig::default()).expect("default patterns are valid")
+    }
+}
+
+impl Rule for UndefinedClassRule {
+    fn name(&self) -> &'static str {
+        "undefined-class"
+    }
+
+    fn description(&self) -> &'static str {
+        "Classes in the template should have a matching rule in the style section"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let ignore = &self.ignore;
+        // Components without styles of their own rely on global classes
+        if source.section(SectionKind::Style).is_none() {
+            return Ok(vec![]);
+        }
+
+        let defined: HashSet<&str> = source
+            .stylesheet()
+            .rules
+            .iter()
+            .flat_map(|rule| &rule.selectors)
+            .flat_map(Selector::class_names)
+            .map(|name| name.name.as_str())
+            .collect();
+
+        let mut issues = vec![];
+        for element in source.template().elements() {
+            // Classes on a component are styled by the component itself
+            if is_component(element) {
+                continue;
+            }
+            let Some(attribute) = element.attribute("class") else {
+                continue;
+            };
+            let (Some(value), Some(value_span)) = (&attribute.value, attribute.value_span) else {
+                continue;
+            };
+            for (offset, class) in words(value) {
+                if defined.contains(class) || ignore.iter().any(|p| p.is_match(class)) {
+                    continue;
+                }
+                let span = Span::new(
+                    value_span.start + offset,
+                    value_span.start + offset + class.len(),
+                );
+                let suggestion = closest_match(class, defined.iter().copied());
+                let mut issue = issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    span,
+                    Severity::Warning,
+                    match suggestion {
+                        Some(suggestion) => format!(
+                            "Class '{class}' has no matching rule in the style section; did you mean '{suggestion}'?"
+                        ),
+                        None => format!("Class '{class}' has no matching rule in the style section"),
+                    },
+                );
+                issue.fix = suggestion.map(|suggestion| {
+                    Fix::new(
+                        format!("Rename class to '{suggestion}'"),
+                        vec![Edit::replace(span, suggestion)],
+                    )
+                });
+                issues.push(issue);
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for selectors that style elements outside the component
+pub struct UnscopedSelectorRule {
+    scoped: bool,
+}
+
+impl UnscopedSelectorRule {
+    pub fn new(config: UnscopedSelectorConfig) -> Self {
+        Self {
+            scoped: config.scoped,
+        }
+    }
+}
+
+impl Default for UnscopedSelectorRule {
+    fn default() -> Self {
+        Self::new(UnscopedSelectorConfig::default())
+    }
+}
+
+impl Rule for UnscopedSelectorRule {
+    fn name(&self) -> &'static str {
+        "unscoped-selector"
+    }
+
+    fn description(&self) -> &'static str {
+        "Component styles should be scoped to the component instead of styling bare elements"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let root = root_element(source);
+        let root_class = root.and_then(|element| element.classes().next());
+        let mut descendants = vec![];
+        source.template().visit(&mut |node, ancestors| {
+            if let (Node::Element(element), false) = (node, ancestors.is_empty()) {
+                descendants.push((element, ancestors.to_vec()));
+            }
+        });
+
+        let mut issues = vec![];
+        // Nested rules are unscoped only when their parent is, which is reported instead
+        for rule in source.stylesheet().rules.iter().filter(|rule| !rule.nested) {
+            for selector in &rule.selectors {
+                if selector.compounds.iter().any(is_foreign) {
+                    continue;
+                }
+                let Some(subject) = selector.subject() else {
+                    continue;
+                };
+                if is_document_root(subject) {
+                    issues.push(issue_at(
+                        self.name(),
+                        source,
+                        file_path,
+                        selector.span,
+                        Severity::Warning,
+                        format!(
+                            "Selector '{}' styles the whole document rather than this component; move it to a global stylesheet",
+                            selector.text
+                        ),
+                    ));
+                    continue;
+                }
+                // Scoped styles only apply to the component's own elements
+                if self.scoped
+                    || selector
+                        .compounds
+                        .iter()
+                        .any(|c| !c.classes.is_empty() || !c.ids.is_empty())
+                {
+                    continue;
+                }
+
+                // A selector for the root element alone can be replaced by the root class
+                let only_root = root.is_some_and(|element| selector.matches(element, &[]))
+                    && !descendants
+                        .iter()
+                        .any(|(element, ancestors)| selector.matches(element, ancestors));
+                let advice = match root_class {
+                    Some(class) if only_root => {
+                        format!("scope it to the component's root class '.{class}'")
+                    }
+                    Some(class) => format!(
+                        "scope it to the component's root class, e.g. '.{class} {}'",
+                        selector.text
+                    ),
+                    None => {
+                        "give the component's root element a class and scope the selector to it"
+                            .to_string()
+                    }
+                };
+                let mut issue = issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    selector.span,
+                    Severity::Warning,
+                    format!(
+                        "Selector '{}' is unscoped and styles every matching element on the page; {advice}",
+                        selector.text
+                    ),
+                );
+                if let (Some(class), false) = (root_class, only_root) {
+                    issue.fix = Some(Fix::new(
+                        format!("Scope selector to '.{class}'"),
+                        vec![Edit::insert(selector.span.start, format!(".{class} "))],
+                    ));
+                }
+                issues.push(issue);
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for selectors repeated across the style section
+pub struct NoDuplicateSelectorsRule;
+
+impl Rule for NoDuplicateSelectorsRule {
+    fn name(&self) -> &'static str {
+        "no-duplicate-selectors"
+    }
+
+    fn description(&self) -> &'static str {
+        "A selector should be defined once per at-rule context instead of being split across rules"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+        let mut seen: HashMap<(Vec<String>, String), Span> = HashMap::new();
+        // Nested selectors depend on their parent, so only top-level rules are compared
+        for rule in source.stylesheet().rules.iter().filter(|rule| !rule.nested) {
+            for selector in &rule.selectors {
+                let key = (rule.conditions.clone(), normalize_selector(&selector.text));
+                match seen.get(&key) {
+                    Some(first) => issues.push(issue_at(
+                        self.name(),
+                        source,
+                        file_path,
+                        selector.span,
+                        Severity::Warning,
+                        format!(
+                            "Selector '{}' is already defined on line {}; merge the declarations into one rule",
+                            selector.text,
+                            source.position(first.start).0
+                        ),
+                    )),
+                    None => {
+                        seen.insert(key, selector.span);
+                    }
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for properties declared more than once in a rule
+pub struct NoDuplicateDeclarationsRule;
+
+impl Rule for NoDuplicateDeclarationsRule {
+    fn name(&self) -> &'static str {
+        "no-duplicate-declarations"
+    }
+
+    fn description(&self) -> &'static str {
+        "A property should be declared once per rule, except for consecutive fallbacks"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+        for rule in &source.stylesheet().rules {
+            for (index, declaration) in rule.declarations.iter().enumerate() {
+                let property = declaration.property.to_lowercase();
+                let Some(previous) = rule.declarations[..index]
+                    .iter()
+                    .rposition(|d| d.property.to_lowercase() == property)
+                else {
+                    continue;
+                };
+                let earlier = &rule.declarations[previous];
+                let line = source.position(earlier.span.start).0;
+                let same = normalize_value(&earlier.value) == normalize_value(&declaration.value)
+                    && earlier.important == declaration.important;
+
+                if same {
+                    let mut issue = issue_at(
+                        self.name(),
+                        source,
+                        file_path,
+                        declaration.property_span,
+                        Severity::Warning,
+                        format!(
+                            "Duplicate declaration '{}: {}'; it is already declared on line {line}",
+                            declaration.property, declaration.value
+                        ),
+                    );
+                    issue.fix = Some(Fix::new(
+                        "Remove duplicate declaration",
+                        vec![Edit::delete(
+                            source.removal_span(with_semicolon(source, declaration.span)),
+                        )],
+                    ));
+                    issues.push(issue);
+                } else if previous + 1 < index {
+                    // Consecutive declarations with different values are fallbacks
+                    issues.push(issue_at(
+                        self.name(),
+                        source,
+                        file_path,
+                        declaration.property_span,
+                        Severity::Warning,
+                        format!(
+                            "Property '{}' is already declared on line {line}, and its value '{}' there is overridden",
+                            declaration.property, earlier.value
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for `!important` declarations
+pub struct NoImportantRule;
+
+impl Rule for NoImportantRule {
+    fn name(&self) -> &'static str {
+        "no-important"
+    }
+
+    fn description(&self) -> &'static str {
+        "Declarations should not use !important"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+        for rule in &source.stylesheet().rules {
+            for declaration in rule.declarations.iter().filter(|d| d.important) {
+                let end = declaration.span.end;
+                let start = declaration.value_span.end
+                    + source
+                        .slice(Span::new(declaration.value_span.end, end))
+                        .find('!')
+                        .unwrap_or_default();
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    Span::new(start, end),
+                    Severity::Warning,
+                    format!(
+                        "Avoid !important on '{}'; use a more specific selector or reorder the rules instead",
+                        declaration.property
+                    ),
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for property names that aren't standard CSS properties
+pub struct UnknownPropertyRule {
+    known_properties: Vec<String>,
+}
+
+impl UnknownPropertyRule {
+    pub fn new(config: UnknownPropertyConfig) -> Self {
+        Self {
+            known_properties: config.known_properties,
+        }
+    }
+}
+
+impl Default for UnknownPropertyRule {
+    fn default() -> Self {
+        Self::new(UnknownPropertyConfig::default())
+    }
+}
+
+impl Rule for UnknownPropertyRule {
+    fn name(&self) -> &'static str {
+        "unknown-property"
+    }
+
+    fn description(&self) -> &'static str {
+        "Declarations should use standard CSS properties"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+        for rule in &source.stylesheet().rules {
+            for declaration in &rule.declarations {
+                let property = declaration.property.to_lowercase();
+                // Custom properties and vendor prefixes
+                if property.starts_with('-')
+                    || css::is_known_property(&property)
+                    || self
+                        .known_properties
+                        .iter()
+                        .any(|p| p.to_lowercase() == property)
+                {
+                    continue;
+                }
+
+                let suggestion = closest_match(&property, css::properties());
+                let mut issue = issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    declaration.property_span,
+                    Severity::Error,
+                    match suggestion {
+                        Some(suggestion) => format!(
+                            "Unknown property '{}'; did you mean '{suggestion}'?",
+                            declaration.property
+                        ),
+                        None => format!("Unknown property '{}'", declaration.property),
+                    },
+                );
+                issue.fix = suggestion.map(|suggestion| {
+                    Fix::new(
+                        format!("Rename property to '{suggestion}'"),
+                        vec![Edit::replace(declaration.property_span, suggestion)],
+                    )
+                });
+                issues.push(issue);
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for property values that are malformed
+pub struct InvalidValueRule;
+
+impl Rule for InvalidValueRule {
+    fn name(&self) -> &'static str {
+        "invalid-value"
+    }
+
+    fn description(&self) -> &'static str {
+        "Property values should be well-formed: units attached to numbers, valid colors and lengths with units"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let mut issues = vec![];
+        for declaration in source
+            .stylesheet()
+            .rules
+            .iter()
+            .flat_map(|rule| &rule.declarations)
+        {
+            let property = declaration.property.to_lowercase();
+            // Values computed at runtime or by a preprocessor can't be checked
+            if property.starts_with("--")
+                || ["var(", "env(", "attr(", "$", "#{", "@"]
+                    .iter()
+                    .any(|dynamic| declaration.value.contains(dynamic))
+            {
+                continue;
+            }
+
+            let start = declaration.value_span.start;
+            let components = value_components(&declaration.value);
+            let mut report = |span: Span, message: String, fix: Option<Fix>| {
+                let mut issue = issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    span,
+                    Severity::Error,
+                    message,
+                );
+                issue.fix = fix;
+                issues.push(issue);
+            };
+
+            let mut detached = HashSet::new();
+            for pair in components.windows(2) {
+                let [(offset, number), (unit_offset, unit)] = pair else {
+                    continue;
+                };
+                let gap = &declaration.value[offset + number.len()..*unit_offset];
+                if is_number(number)
+                    && css::UNITS.contains(&unit.to_lowercase().as_str())
+                    && gap.chars().all(char::is_whitespace)
+                {
+                    detached.insert(*offset);
+                    report(
+                        Span::new(start + offset, start + unit_offset + unit.len()),
+                        format!(
+                            "'{number}{gap}{unit}' has a space between the number and its unit; write '{number}{unit}'"
+                        ),
+                        Some(Fix::new(
+                            format!("Write '{number}{unit}'"),
+                            vec![Edit::delete(Span::new(
+                                start + offset + number.len(),
+                                start + unit_offset,
+                            ))],
+                        )),
+                    );
+                }
+            }
+
+            for (offset, component) in &components {
+                let span = Span::new(start + offset, start + offset + component.len());
+                let lower = component.to_lowercase();
+                let color_function = ["rgb(", "rgba(", "hsl(", "hsla("]
+                    .iter()
+                    .any(|function| lower.starts_with(function));
+                if (component.starts_with('#') || color_function)
+                    && parse_color(component).is_none()
+                {
+                    report(
+                        span,
+                        format!(
+                            "'{component}' is not a valid color for '{}'",
+                            declaration.property
+                        ),
+                        None,
+                    );
+                } else if css::LENGTH_PROPERTIES.contains(&property.as_str())
+                    && is_number(component)
+                    && component.parse::<f64>().is_ok_and(|n| n != 0.0)
+                    && !detached.contains(offset)
+                {
+                    report(
+                        span,
+                        format!(
+                            "Length '{component}' for '{}' needs a unit, such as '{component}px'",
+                            declaration.property
+                        ),
+                        None,
+                    );
+                }
+            }
+
+            // A single word in a color property must be a color keyword
+            if let ([(_, word)], true) = (
+                components.as_slice(),
+                css::COLOR_PROPERTIES.contains(&property.as_str()),
+            ) {
+                let lower = word.to_lowercase();
+                let keyword = [
+                    "currentcolor",
+                    "none",
+                    "auto",
+                    "context-fill",
+                    "context-stroke",
+                ]
+                .contains(&lower.as_str())
+                    || css::WIDE_KEYWORDS.contains(&lower.as_str());
+                if word.chars().all(|c| c.is_ascii_alphabetic() || c == '-')
+                    && !keyword
+                    && parse_color(word).is_none()
+                {
+                    let suggestion = closest_match(&lower, color_names());
+                    report(
+                        declaration.value_span,
+                        match suggestion {
+                            Some(suggestion) => format!(
+                                "'{word}' is not a valid color for '{}'; did you mean '{suggestion}'?",
+                                declaration.property
+                            ),
+                            None => format!(
+                                "'{word}' is not a valid color for '{}'",
+                                declaration.property
+                            ),
+                        },
+                        suggestion.map(|suggestion| {
+                            Fix::new(
+                                format!("Replace with '{suggestion}'"),
+                                vec![Edit::replace(declaration.value_span, suggestion)],
+                            )
+                        }),
+                    );
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for hardcoded colors, spacing, radii and font values that should use design tokens
+pub struct DesignTokensRule {
+    tokens: DesignTokens,
+    reference: String,
+}
+
+impl DesignTokensRule {
+    /// Load the configured token file, failing if it cannot be read or parsed
+    pub fn new(config: DesignTokensConfig) -> Result<Self, String> {
+        let tokens = match &config.tokens_file {
+            Some(path) => DesignTokens::load(path)?,
+            None => DesignTokens::default(),
+        };
+        Ok(Self {
+            tokens,
+            reference: config.reference,
+        })
+    }
+
+    /// Issues for the hardcoded values of a declaration
+    fn check_declaration(
+        &self,
+        tokens: &DesignTokens,
+        declaration: &Declaration,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Vec<Issue> {
+        hardcoded_values(declaration)
+            .into_iter()
+            .map(|(span, literal, category)| {
+                let kind = category.name();
+                let Some(token) = tokens.matching(literal, category) else {
+                    return issue_at(
+                        self.name(),
+                        source,
+                        file_path,
+                        span,
+                        Severity::Warning,
+                        format!(
+                            "Hardcoded {kind} '{literal}' in '{}'; use a {kind} design token instead",
+                            declaration.property
+                        ),
+                    );
+                };
+                let reference = self.reference.replace("{name}", &token.name.replace('.', "-"));
+                let mut issue = issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    span,
+                    Severity::Warning,
+                    format!(
+                        "Hardcoded {kind} '{literal}' in '{}' matches the design token '{}'; use {reference} instead",
+                        declaration.property, token.name
+                    ),
+                );
+                issue.fix = Some(Fix::new(
+                    format!("Replace with {reference}"),
+                    vec![Edit::replace(span, reference)],
+                ));
+                issue
+            })
+            .collect()
+    }
+}
+
+impl Default for DesignTokensRule {
+    fn default() -> Self {
+        Self {
+            tokens: DesignTokens::default(),
+            reference: DesignTokensConfig::default().reference,
+        }
+    }
+}
+
+impl Rule for DesignTokensRule {
+    fn name(&self) -> &'static str {
+        "design-tokens"
+    }
+
+    fn description(&self) -> &'static str {
+        "Colors, spacing, radii and font values should come from the design tokens"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let tokens = &self.tokens;
+        let mut issues = vec![];
+        for declaration in source
+            .stylesheet()
+            .rules
+            .iter()
+            .flat_map(|rule| &rule.declarations)
+        {
+            issues.extend(self.check_declaration(tokens, declaration, source, file_path));
+        }
+        for element in source.template().elements() {
+            let Some(span) = element.attribute("style").and_then(|a| a.value_span) else {
+                continue;
+            };
+            for declaration in parse_declarations(source.text(), span) {
+                issues.extend(self.check_declaration(tokens, &declaration, source, file_path));
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Classes the template may apply to its elements
+#[derive(Default)]
+struct TemplateClasses {
+    /// Names from `class` attributes, resolved bindings and string literals in the script
+    names: HashSet<String>,
+    /// Static prefixes of template literal classes such as `btn-${size}`
+    prefixes: Vec<String>,
+    /// Whether the template has any class binding
+    bound: bool,
+    /// Whether a binding may apply classes that can't be known statically
+    dynamic: bool,
+}
+
+impl TemplateClasses {
+    fn collect(source: &SourceFile) -> Self {
+        let mut classes = Self::default();
+        for element in source.template().elements() {
+            classes.names.extend(element.classes().map(str::to_string));
+            for attribute in &element.attributes {
+                match attribute.name.as_str() {
+                    ":class" | "v-bind:class" => {
+                        classes.bound = true;
+                        let (Some(value), Some(span)) = (&attribute.value, attribute.value_span)
+                        else {
+                            continue;
+                        };
+                        classes.value(&tokenize(value, span.start));
+                    }
+                    // Spread bindings may set a class
+                    "v-bind" => {
+                        classes.bound = true;
+                        classes.dynamic = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Classes toggled from methods, such as `classList.add('open')`
+        if let Some(script) = source.section(SectionKind::Script) {
+            for token in tokenize(source.slice(script.content), script.content.start) {
+                if token.kind == TokenKind::Str {
+                    classes.literal(&token.text, false);
+                }
+            }
+        }
+        classes
+    }
+
+    fn contains(&self, class: &str) -> bool {
+        self.dynamic
+            || self.names.contains(class)
+            || self.prefixes.iter().any(|p| class.starts_with(p.as_str()))
+    }
+
+    /// Collect the classes a `:class` value may apply
+    fn value(&mut self, tokens: &[Token]) {
+        let tokens = without_parens(tokens);
+        let enclosed = |open: &str, close: &str| {
+            tokens.first().is_some_and(|t| t.is_punct(open))
+                && tokens.last().is_some_and(|t| t.is_punct(close))
+                && group_end(tokens, 0) == Some(tokens.len() - 1)
+        };
+
+        match tokens {
+            [] => {}
+            [token] if token.kind == TokenKind::Str => self.literal(&token.text, true),
+            [token]
+                if token.kind == TokenKind::Ident
+                    && matches!(token.text.as_str(), "null" | "undefined" | "false" | "true") => {}
+            _ if enclosed("{", "}") => self.object(&tokens[1..tokens.len() - 1]),
+            _ if enclosed("[", "]") => {
+                for item in split_top_level(&tokens[1..tokens.len() - 1], ",") {
+                    self.value(item);
+                }
+            }
+            _ => {
+                if let Some(question) = find_top_level(tokens, "?") {
+                    // `condition ? 'a' : 'b'`
+                    let branches = &tokens[question + 1..];
+                    match ternary_colon(branches) {
+                        Some(colon) => {
+                            self.value(&branches[..colon]);
+                            self.value(&branches[colon + 1..]);
+                        }
+                        None => self.dynamic = true,
+                    }
+                } else if let Some(and) = tokens.iter().rposition(|t| t.is_punct("&&")) {
+                    // `condition && 'a'`
+                    self.value(&tokens[and + 1..]);
+                } else {
+                    self.dynamic = true;
+                }
+            }
+        }
+    }
+
+    /// Collect the keys of a `{ class: condition }` object
+    fn object(&mut self, tokens: &[Token]) {
+        for entry in split_top_level(tokens, ",") {
+            let key = match find_top_level(entry, ":") {
+                Some(colon) => &entry[..colon],
+                None => entry,
+            };
+            match key {
+                [] => {}
+                [token] if token.kind == TokenKind::Ident => {
+                    self.names.insert(token.text.clone());
+                }
+                [token] if token.kind == TokenKind::Str => self.literal(&token.text, true),
+                // Computed keys and spreads
+                _ => self.dynamic = true,
+            }
+        }
+    }
+
+    /// Collect the words of a string literal; `binding` marks literals used as class values
+    fn literal(&mut self, text: &str, binding: bool) {
+        let quote = &text[..1];
+        let content = text[1..].strip_suffix(quote).unwrap_or(&text[1..]);
+        for word in content.split_whitespace() {
+            match word.find("${") {
+                None => {
+                    self.names.insert(word.to_string());
+                }
+                Some(0) => self.dynamic |= binding,
+                Some(start) => self.prefixes.push(word[..start].to_string()),
+            }
+        }
+    }
+}
+
+/// Whether the compound selects markup rendered by another component
+fn is_foreign(compound: &Compound) -> bool {
+    compound
+        .pseudo_classes
+        .iter()
+        .any(|p| FOREIGN_PSEUDO_CLASSES.contains(&p.name.as_str()))
+        || compound.pseudo_element.as_deref() == Some("v-deep")
+}
+
+/// Whether the compound selects the document rather than an element of the component
+fn is_document_root(compound: &Compound) -> bool {
+    compound
+        .tag
+        .as_deref()
+        .is_some_and(|tag| tag.eq_ignore_ascii_case("html") || tag.eq_ignore_ascii_case("body"))
+        || compound.pseudo_classes.iter().any(|p| p.name == "root")
+}
+
+/// The template's single root element, if it has one
+fn root_element(source: &SourceFile) -> Option<&Element> {
+    let mut elements = source
+        .template()
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            _ => None,
+        });
+    let root = elements.next()?;
+    elements.next().is_none().then_some(root)
+}
+
+/// The selector without leading `html`, `body` and `:root` compounds, which are outside the
+/// template; `None` if nothing is left
+fn without_document_root(selector: &Selector) -> Option<Selector> {
+    let start = selector
+        .compounds
+        .iter()
+        .position(|c| !is_document_root(c))?;
+    Some(Selector {
+        compounds: selector.compounds[start..].to_vec(),
+        ..selector.clone()
+    })
+}
+
+/// Selector text with insignificant whitespace removed
+fn normalize_selector(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    [">", "+", "~", ","]
+        .iter()
+        .fold(collapsed, |text, combinator| {
+            text.replace(&format!(" {combinator}"), combinator)
+                .replace(&format!("{combinator} "), combinator)
+        })
+}
+
+/// Declaration value compared case-insensitively and ignoring whitespace differences
+fn normalize_value(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// The declaration's span extended over its trailing semicolon
+fn with_semicolon(source: &SourceFile, span: Span) -> Span {
+    let end = span.end + 1;
+    match source.slice(Span::new(span.start, end)).ends_with(';') {
+        true => Span::new(span.start, end),
+        false => span,
+    }
+}
+
+/// Space, comma and slash separated components of a value with their byte offsets;
+/// functions and quoted strings are kept whole
+fn value_components(value: &str) -> Vec<(usize, &str)> {
+    let mut components = vec![];
+    let mut start = None;
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (i, c) in value.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                start.get_or_insert(i);
+            }
+            (None, '(') => {
+                depth += 1;
+                start.get_or_insert(i);
+            }
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, _) if depth == 0 && (c.is_whitespace() || c == ',' || c == '/') => {
+                if let Some(s) = start.take() {
+                    components.push((s, &value[s..i]));
+                }
+            }
+            _ => {
+                start.get_or_insert(i);
+            }
+        }
+    }
+    if let Some(s) = start {
+        components.push((s, &value[s..]));
+    }
+    components
+}
+
+/// Whether the text is a plain number without a unit
+fn is_number(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '.' | '-' | '+'))
+        && text.contains(|c: char| c.is_ascii_digit())
+        && text.parse::<f64>().is_ok()
+}
+
+/// Literal values of a declaration that a design token could replace
+fn hardcoded_values(declaration: &Declaration) -> Vec<(Span, &str, TokenCategory)> {
+    let property = declaration.property.to_lowercase();
+    let start = declaration.value_span.start;
+    // Custom properties define tokens of their own
+    if property.starts_with("--") || declaration.value.contains("var(") {
+        return vec![];
+    }
+    if property == "font-family" {
+        if css::WIDE_KEYWORDS.contains(&declaration.value.to_lowercase().as_str()) {
+            return vec![];
+        }
+        return vec![(
+            declaration.value_span,
+            &declaration.value,
+            TokenCategory::Font,
+        )];
+    }
+
+    let mut values = vec![];
+    for (offset, component) in value_components(&declaration.value) {
+        let span = Span::new(start + offset, start + offset + component.len());
+        let lower = component.to_lowercase();
+        let is_color = match lower.split_once('(') {
+            Some((function, _)) => ["rgb", "rgba", "hsl", "hsla"].contains(&function),
+            None => {
+                lower.starts_with('#')
+                    || (lower != "transparent"
+                        && !NAMED_VALUE_PROPERTIES
+                            .iter()
+                            .any(|p| property.starts_with(p)))
+            }
+        };
+        if is_color && parse_color(component).is_some() {
+            values.push((span, component, TokenCategory::Color));
+            continue;
+        }
+
+        let category = if property.contains("radius") {
+            TokenCategory::Radius
+        } else if ["margin", "padding", "inset"]
+            .iter()
+            .any(|p| property.starts_with(p))
+            || property.ends_with("gap")
+        {
+            TokenCategory::Spacing
+        } else if ["font-size", "font-weight", "line-height", "letter-spacing"]
+            .contains(&property.as_str())
+        {
+            TokenCategory::Font
+        } else {
+            continue;
+        };
+        // Font weights and line heights may be plain numbers
+        let plain_number = category == TokenCategory::Font
+            && matches!(property.as_str(), "font-weight" | "line-height")
+            && is_number(component);
+        if (plain_number || is_dimension(component))
+            && component
+                .trim_start_matches(['-', '+', '0', '.'])
+                .starts_with(|c: char| c.is_ascii_digit())
+        {
+            values.push((span, component, category));
+        }
+    }
+    values
+}
+
+/// Whether the text is a number with a length unit, such as `8px`
+fn is_dimension(text: &str) -> bool {
+    let unit_start = text
+        .rfind(|c: char| c.is_ascii_digit() || c == '.')
+        .map_or(0, |i| i + 1);
+    let (number, unit) = text.split_at(unit_start);
+    unit != "%" && is_number(number) && css::UNITS.contains(&unit.to_lowercase().as_str())
+}
+
+/// Tokens without parentheses wrapping the whole expression
+fn without_parens(mut tokens: &[Token]) -> &[Token] {
+    while tokens.first().is_some_and(|t| t.is_punct("("))
+        && group_end(tokens, 0) == Some(tokens.len() - 1)
+    {
+        tokens = &tokens[1..tokens.len() - 1];
+    }
+    tokens
+}
+
+/// Index of the bracket closing the one at `open`
+fn group_end(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if token.kind != TokenKind::Punct {
+            continue;
+        }
+        match token.text.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Index of the first `punct` outside brackets
+fn find_top_level(tokens: &[Token], punct: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::Punct {
+            continue;
+        }
+        match token.text.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth = depth.saturating_sub(1),
+            text if depth == 0 && text == punct => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split tokens at every `punct` outside brackets
+fn split_top_level<'t>(mut tokens: &'t [Token], punct: &str) -> Vec<&'t [Token]> {
+    let mut parts = vec![];
+    while let Some(i) = find_top_level(tokens, punct) {
+        parts.push(&tokens[..i]);
+        tokens = &tokens[i + 1..];
+    }
+    parts.push(tokens);
+    parts
+}
+
+/// Index of the `:` separating the branches of a ternary, skipping nested ternaries
+fn ternary_colon(tokens: &[Token]) -> Option<usize> {
+    let mut nested = 0usize;
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::Punct {
+            continue;
+        }
+        match token.text.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth = depth.saturating_sub(1),
+            "?" if depth == 0 => nested += 1,
+            ":" if depth == 0 => match nested.checked_sub(1) {
+                Some(remaining) => nested = remaining,
+                None => return Some(i),
+            },
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,447 @@
+// Rules for template markup
+// These check the template against the component declared in the script section
+
+use crate::reporter::{Issue, Severity};
+use crate::rules::{closest_match, issue_at, Rule};
+use crate::source::component::{Component, MemberKind, Param};
+use crate::source::expression::{expressions, Expression};
+use crate::source::script::{substitutions, tokenize, TokenKind};
+use crate::source::template::{Attribute, Node};
+use crate::source::{SourceFile, Span};
+use orbit::parser::OrbitAst;
+
+/// Rule for identifiers in template expressions that the component doesn't declare
+pub struct UndefinedTemplateReferenceRule;
+
+impl Rule for UndefinedTemplateReferenceRule {
+    fn name(&self) -> &'static str {
+        "undefined-template-reference"
+    }
+
+    fn description(&self) -> &'static str {
+        "Identifiers used in the template must be declared as props, state or methods"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        // Without a component there is nothing to resolve against
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+
+        let mut issues = vec![];
+        for expression in expressions(source) {
+            // Event handlers are checked by `undefined-event-handler`
+            let handler = expression.handler().map(|h| h.span);
+            for reference in expression.references() {
+                if component.member(&reference.name).is_some() || handler == Some(reference.span) {
+                    continue;
+                }
+
+                let mut message = format!(
+                    "'{}' is not declared in the component's props, state or methods",
+                    reference.name
+                );
+                if let Some(suggestion) =
+                    closest_match(&reference.name, component.members().map(|(name, _)| name))
+                {
+                    message.push_str(&format!("; did you mean '{suggestion}'?"));
+                }
+
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    reference.span,
+                    Severity::Error,
+                    message,
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for `@event` directives whose handler is missing, not callable or called with the wrong arguments
+pub struct UndefinedEventHandlerRule;
+
+impl Rule for UndefinedEventHandlerRule {
+    fn name(&self) -> &'static str {
+        "undefined-event-handler"
+    }
+
+    fn description(&self) -> &'static str {
+        "Event handlers must reference a method or callback prop that accepts the given arguments"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let Some(component) = source.component() else {
+            return Ok(vec![]);
+        };
+
+        let mut issues = vec![];
+        for expression in expressions(source) {
+            let (Some(handler), Some(attribute)) = (expression.handler(), expression.attribute)
+            else {
+                continue;
+            };
+            let event = &attribute.name;
+
+            let message = match component.member(&handler.name) {
+                None => {
+                    let mut message = format!(
+                        "Event handler '{}' for {event} is not a method or callback prop of the component",
+                        handler.name
+                    );
+                    if let Some(suggestion) =
+                        closest_match(&handler.name, callable_members(component))
+                    {
+                        message.push_str(&format!("; did you mean '{suggestion}'?"));
+                    }
+                    Some(message)
+                }
+                Some(MemberKind::State) => Some(format!(
+                    "Event handler '{}' for {event} is a state variable, not a method or callback prop",
+                    handler.name
+                )),
+                Some(MemberKind::Prop) => {
+                    let prop = component.prop(&handler.name).expect("member is a prop");
+                    match prop.type_annotation.as_ref() {
+                        // Untyped props may well be callbacks
+                        None => None,
+                        Some(ty) => match function_arity(&ty.text) {
+                            None => Some(format!(
+                                "Event handler '{}' for {event} is a prop of type '{}', not a callback",
+                                handler.name, ty.text
+                            )),
+                            Some(arity) => arity_mismatch("Callback prop", &handler.name, arity, handler.arguments, event),
+                        },
+                    }
+                }
+                Some(MemberKind::Method) => {
+                    let method = component.method(&handler.name).expect("member is a method");
+                    arity_mismatch(
+                        "Method",
+                        &handler.name,
+                        params_arity(&method.params),
+                        handler.arguments,
+                        event,
+                    )
+                }
+            };
+
+            if let Some(message) = message {
+                issues.push(issue_at(
+                    self.name(),
+                    source,
+                    file_path,
+                    attribute.span,
+                    Severity::Error,
+                    message,
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Rule for `id` attributes that repeat within a component, or that a `v-for` loop repeats
+pub struct NoDuplicateIdsRule;
+
+impl Rule for NoDuplicateIdsRule {
+    fn name(&self) -> &'static str {
+        "no-duplicate-ids"
+    }
+
+    fn description(&self) -> &'static str {
+        "Element ids should be unique within a component, including ids inside v-for loops"
+    }
+
+    fn check_source(
+        &self,
+        _ast: &OrbitAst,
+        source: &SourceFile,
+        file_path: &str,
+    ) -> Result<Vec<Issue>, String> {
+        let expressions = expressions(source);
+        let mut ids = vec![];
+        collect_ids(
+            &source.template().nodes,
+            &expressions,
+            &IdContext::default(),
+            &mut ids,
+        );
+
+        let mut issues = vec![];
+        for (i, id) in ids.iter().enumerate() {
+            let message = match &id.value {
+                IdValue::Static(value) if id.in_loop => format!(
+                    "Static id '{value}' is inside a v-for loop, so every iteration gets the same id; include the loop item or index in an :id binding"
+                ),
+                IdValue::Static(value) => {
+                    // Branches of one v-if chain never render together
+                    let Some(first) = ids[..i]
+                        .iter()
+                        .find(|other| other.value == id.value && !other.exclusive_with(id))
+                    else {
+                        continue;
+                    };
+                    format!(
+                        "Duplicate id '{value}'; it is already used on line {}",
+                        source.position(first.span.start).0
+                    )
+                }
+                IdValue::Dynamic { uses_loop: false } if id.in_loop => {
+                    "id binding inside a v-for loop doesn't use a loop variable, so every iteration gets the same id"
+                        .to_string()
+                }
+                IdValue::Dynamic { .. } => continue,
+            };
+            issues.push(issue_at(
+                self.name(),
+                source,
+                file_path,
+                id.span,
+                Severity::Error,
+                message,
+            ));
+        }
+
+        Ok(issues)
+    }
+}
+
+/// An `id` attribute or binding in the template
+struct Id {
+    value: IdValue,
+    /// Span of the attribute value
+    span: Span,
+    /// Whether the element or one of its ancestors has `v-for`
+    in_loop: bool,
+    /// The `v-if` chains the element is in, as (chain, branch) pairs
+    branches: Vec<(usize, usize)>,
+}
+
+#[derive(PartialEq)]
+enum IdValue {
+    /// `id="header"`, or a binding to a string literal such as `:id="'header'"`
+    Static(String),
+    /// Any other binding; `uses_loop` is set when it references a loop variable
+    Dynamic { uses_loop: bool },
+}
+
+impl Id {
+    /// Whether the two ids are in different branches of the same `v-if` chain
+    fn exclusive_with(&self, other: &Id) -> bool {
+        self.branches.iter().any(|(chain, branch)| {
+            other
+                .branches
+                .iter()
+                .any(|(other_chain, other_branch)| chain == other_chain && branch != other_branch)
+        })
+    }
+}
+
+/// Loop and `v-if` chain context of the nodes being visited
+#[derive(Clone, Default)]
+struct IdContext {
+    in_loop: bool,
+    branches: Vec<(usize, usize)>,
+}
+
+/// Collect the ids of `nodes` and their descendants in document order
+fn collect_ids(nodes: &[Node], expressions: &[Expression], context: &IdContext, ids: &mut Vec<Id>) {
+    // The current `v-if` chain among these siblings, as (chain, branch)
+    let mut chain: Option<(usize, usize)> = None;
+
+    for node in nodes {
+        let Node::Element(element) = node else {
+            continue;
+        };
+
+        chain = if element.has_attribute("v-if") {
+            Some((element.span.start, 0))
+        } else if element.has_attribute("v-else-if") || element.has_attribute("v-else") {
+            chain.map(|(chain, branch)| (chain, branch + 1))
+        } else {
+            None
+        };
+
+        let mut context = context.clone();
+        context.in_loop |= element.has_attribute("v-for");
+        context.branches.extend(chain);
+
+        for attribute in &element.attributes {
+            let Some(span) = attribute.value_span else {
+                continue;
+            };
+            let value = match attribute.name.as_str() {
+                "id" => IdValue::Static(attribute.value.clone().unwrap_or_default()),
+                ":id" | "v-bind:id" => id_binding(attribute, expressions),
+                _ => continue,
+            };
+            ids.push(Id {
+                value,
+                span,
+                in_loop: context.in_loop,
+                branches: context.branches.clone(),
+            });
+        }
+
+        collect_ids(&element.children, expressions, &context, ids);
+    }
+}
+
+/// Value of an `:id` binding: static when it is a plain string literal
+fn id_binding(attribute: &Attribute, expressions: &[Expression]) -> IdValue {
+    let Some(expression) = expressions
+        .iter()
+        .find(|e| e.attribute.is_some_and(|a| std::ptr::eq(a, attribute)))
+    else {
+        return IdValue::Dynamic { uses_loop: false };
+    };
+
+    let tokens = tokenize(expression.text, expression.span.start);
+    if let [token] = tokens.as_slice() {
+        if token.kind == TokenKind::Str && !token.text.contains("${") {
+            let quote = &token.text[..1];
+            let text = &token.text[1..];
+            return IdValue::Static(text.strip_suffix(quote).unwrap_or(text).to_string());
+        }
+    }
+
+    // Template literals are a single token, so look inside their `${...}` parts too
+    let interpolated = tokens.iter().flat_map(substitutions).flatten();
+    let uses_loop = tokens
+        .iter()
+        .cloned()
+        .chain(interpolated)
+        .any(|t| t.kind == TokenKind::Ident && expression.locals.contains(&t.text));
+
+    IdValue::Dynamic { uses_loop }
+}
+
+/// Minimum and maximum number of arguments a callable accepts
+type Arity = (usize, usize);
+
+/// Methods and props with function types
+fn callable_members(component: &Component) -> impl Iterator<Item = &str> {
+    let methods = component.methods.iter().map(|m| m.name.as_str());
+    let callbacks = component
+        .props
+        .iter()
+        .filter(|p| {
+            p.type_annotation
+                .as_ref()
+                .is_some_and(|ty| function_arity(&ty.text).is_some())
+        })
+        .map(|p| p.name.as_str());
+    methods.chain(callbacks)
+}
+
+fn params_arity(params: &[Param]) -> Arity {
+    let required = params.iter().filter(|p| !p.optional && !p.variadic).count();
+    let max = if params.iter().any(|p| p.variadic) {
+        usize::MAX
+    } else {
+        params.len()
+    };
+    (required, max)
+}
+
+/// Arity of a function type such as `(item: Item) => void` or `Fn(Item)`, or `None` for other types
+fn function_arity(ty: &str) -> Option<Arity> {
+    let tokens = tokenize(ty, 0);
+    let open = if tokens.first()?.is_punct("(") {
+        // Arrow function types: the parameter list must be followed by `=>`
+        0
+    } else if ["Fn", "FnMut", "FnOnce", "fn", "Function"].contains(&tokens[0].text.as_str()) {
+        if tokens[0].text == "Function" && tokens.len() == 1 {
+            return Some((0, usize::MAX));
+        }
+        1
+    } else {
+        return None;
+    };
+    tokens.get(open).filter(|t| t.is_punct("("))?;
+
+    let mut depth = 0;
+    let mut close = None;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token.text.as_str() {
+            "(" | "[" | "{" | "<" => depth += 1,
+            ")" | "]" | "}" | ">" => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            close = Some(i);
+            break;
+        }
+    }
+    let close = close?;
+    if open == 0 && !tokens.get(close + 1)?.is_punct("=>") {
+        return None;
+    }
+
+    // Count top-level parameters; `name?:` is optional and `...rest` accepts any number
+    let inner = &tokens[open + 1..close];
+    if inner.is_empty() {
+        return Some((0, 0));
+    }
+    let (mut required, mut max, mut depth) = (0, 0, 0);
+    let (mut optional, mut variadic) = (false, false);
+    for (i, token) in inner.iter().enumerate() {
+        match token.text.as_str() {
+            "(" | "[" | "{" | "<" => depth += 1,
+            ")" | "]" | "}" | ">" => depth -= 1,
+            "?" if depth == 0 => optional = true,
+            "..." if depth == 0 => variadic = true,
+            _ => {}
+        }
+        if depth == 0 && (token.is_punct(",") || i == inner.len() - 1) {
+            max += 1;
+            if !optional && !variadic {
+                required += 1;
+            }
+            optional = false;
+        }
+    }
+    Some((required, if variadic { usize::MAX } else { max }))
+}
+
+/// Describe an argument count mismatch between a handler and the directive calling it
+fn arity_mismatch(
+    what: &str,
+    name: &str,
+    (required, max): Arity,
+    arguments: Option<usize>,
+    event: &str,
+) -> Option<String> {
+    match arguments {
+        // A bare handler is called with the event
+        None if required > 1 => Some(format!(
+            "{what} '{name}' requires {required} arguments, but {event} only passes the event"
+        )),
+        None => None,
+        Some(count) if count < required => Some(format!(
+            "{what} '{name}' requires {required} argument{}, but {event} passes {count}",
+            if required == 1 { "" } else { "s" }
+        )),
+        Some(count) if count > max => Some(format!(
+            "{what} '{name}' accepts {max} argument{}, but {event} passes {count}",
+            if max == 1 { "" } else { "s" }
+        )),
+        Some(_) => None,
+    }
+}
@@ -0,0 +1,153 @@
+// Design token files
+// Loads the tokens of a design system from a JSON or TOML file. Groups nest as objects and a
+// token is either a plain value or an object with a `$value` (or `value`) entry, so both
+// hand-written files and the W3C and Style Dictionary formats work.
+
+use crate::rules::color::parse_color;
+use serde_json::Value;
+use std::path::Path;
+
+/// Kind of value a token or a hardcoded literal stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenCategory {
+    Color,
+    Spacing,
+    Radius,
+    Font,
+}
+
+impl TokenCategory {
+    /// Lowercase name used in messages
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Color => "color",
+            Self::Spacing => "spacing",
+            Self::Radius => "radius",
+            Self::Font => "font",
+        }
+    }
+
+    /// Category named by a `$type` entry or a group in the token's path
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.contains("color") || name.contains("colour") || name == "palette" {
+            Some(Self::Color)
+        } else if name.contains("radius") || name.contains("radii") || name == "rounded" {
+            Some(Self::Radius)
+        } else if name.contains("spac")
+            || ["gap", "gutter", "margin", "padding"].contains(&name.as_str())
+        {
+            Some(Self::Spacing)
+        } else if name.contains("font") || name.contains("typography") || name == "lineheight" {
+            Some(Self::Font)
+        } else {
+            None
+        }
+    }
+}
+
+/// A design token
+#[derive(Debug, Clone)]
+pub(crate) struct Token {
+    /// Dotted path of the token, such as `color.primary`
+    pub name: String,
+    /// Value as written in the token file
+    pub value: String,
+    /// Category from the token's `$type` or groups, or inferred from a color value
+    pub category: Option<TokenCategory>,
+}
+
+/// Tokens loaded from a token file
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DesignTokens {
+    pub tokens: Vec<Token>,
+}
+
+impl DesignTokens {
+    /// Load tokens from a `.json` or `.toml` file
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read design tokens file '{path}': {e}"))?;
+        let is_toml = Path::new(path)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"));
+        let value = if is_toml {
+            toml::from_str::<toml::Value>(&content)
+                .map_err(|e| e.to_string())
+                .and_then(|value| serde_json::to_value(value).map_err(|e| e.to_string()))
+        } else {
+            serde_json::from_str(&content).map_err(|e| e.to_string())
+        }
+        .map_err(|e| format!("invalid design tokens file '{path}': {e}"))?;
+
+        let mut tokens = DesignTokens::default();
+        tokens.collect(&value, &mut vec![], None);
+        Ok(tokens)
+    }
+
+    fn collect<'v>(
+        &mut self,
+        value: &'v Value,
+        path: &mut Vec<&'v str>,
+        category: Option<TokenCategory>,
+    ) {
+        // The outermost group that names a category wins, so `font.letterSpacing` stays a font
+        let category =
+            category.or_else(|| path.last().and_then(|name| TokenCategory::from_name(name)));
+        match value {
+            Value::Object(entries) => {
+                let token_value = entries
+                    .get("$value")
+                    .or_else(|| entries.get("value").filter(|v| !v.is_object()));
+                if let Some(token_value) = token_value {
+                    let category = entries
+                        .get("$type")
+                        .or_else(|| entries.get("type"))
+                        .and_then(Value::as_str)
+                        .and_then(TokenCategory::from_name)
+                        .or(category);
+                    self.collect(token_value, path, category);
+                    return;
+                }
+                for (name, entry) in entries {
+                    if name.starts_with('$') {
+                        continue;
+                    }
+                    path.push(name);
+                    self.collect(entry, path, category);
+                    path.pop();
+                }
+            }
+            Value::String(text) => self.push(path, text.clone(), category),
+            Value::Number(number) => self.push(path, number.to_string(), category),
+            _ => {}
+        }
+    }
+
+    fn push(&mut self, path: &[&str], value: String, category: Option<TokenCategory>) {
+        if path.is_empty() {
+            return;
+        }
+        let category = category.or_else(|| parse_color(&value).map(|_| TokenCategory::Color));
+        self.tokens.push(Token {
+            name: path.join("."),
+            value,
+            category,
+        });
+    }
+
+    /// Token whose value is exactly `literal`, preferring tokens of the literal's category
+    pub fn matching(&self, literal: &str, category: TokenCategory) -> Option<&Token> {
+        let matches = |token: &&Token| token.value.trim().eq_ignore_ascii_case(literal);
+        self.tokens
+            .iter()
+            .filter(matches)
+            .find(|token| token.category == Some(category))
+            .or_else(|| {
+                self.tokens
+                    .iter()
+                    .filter(matches)
+                    .find(|token| token.category.is_none())
+            })
+    }
+}
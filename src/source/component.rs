@@ -0,0 +1,407 @@
+// Component model for the script section
+// Recovers the component's name, props, state and methods with their spans from the script
+// tokens. The parser is tolerant: anything it doesn't understand is skipped, so rules see
+// whatever members could be recognised instead of failing on unfamiliar syntax.
+
+use super::script::{tokenize, Token, TokenKind};
+use super::Span;
+
+/// A piece of source text with its location, such as a type annotation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    /// Text as written in the source
+    pub text: String,
+    /// Span of the text in the file
+    pub span: Span,
+}
+
+/// A prop or state declaration such as `label: string = "Save";`
+#[derive(Debug, Clone)]
+pub struct Field {
+    /// Field name
+    pub name: String,
+    /// Span of the name
+    pub name_span: Span,
+    /// Whether the field was declared optional with `name?:`
+    pub optional: bool,
+    /// Type annotation, if any
+    pub type_annotation: Option<Fragment>,
+    /// Initial or default value, if any
+    pub initial_value: Option<Fragment>,
+    /// Span of the whole declaration, including the trailing `;` or `,`
+    pub span: Span,
+}
+
+/// A method parameter
+#[derive(Debug, Clone)]
+pub struct Param {
+    /// Parameter name
+    pub name: String,
+    /// Span of the name
+    pub name_span: Span,
+    /// Type annotation, if any
+    pub type_annotation: Option<Fragment>,
+    /// Whether callers may omit the argument (`name?: T` or `name = value`)
+    pub optional: bool,
+    /// Whether this is a rest parameter (`...names`)
+    pub variadic: bool,
+}
+
+/// A method, including lifecycle hooks
+#[derive(Debug, Clone)]
+pub struct Method {
+    /// Method name
+    pub name: String,
+    /// Span of the name
+    pub name_span: Span,
+    /// Parameters; a Rust-style `self` receiver is not included
+    pub params: Vec<Param>,
+    /// Span of the text between the parentheses
+    pub params_span: Span,
+    /// Return type annotation, if any
+    pub return_type: Option<Fragment>,
+    /// Span of the text between the body's braces
+    pub body: Span,
+    /// Span of the whole method, from any modifiers to the closing brace
+    pub span: Span,
+    /// Declared with a `pub` or `public` modifier, so it may be called from outside the component
+    pub public: bool,
+}
+
+/// Kind of a component member
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberKind {
+    Prop,
+    State,
+    Method,
+}
+
+impl std::fmt::Display for MemberKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemberKind::Prop => write!(f, "prop"),
+            MemberKind::State => write!(f, "state variable"),
+            MemberKind::Method => write!(f, "method"),
+        }
+    }
+}
+
+/// A component declared in the script section
+#[derive(Debug, Clone)]
+pub struct Component {
+    /// Component name
+    pub name: String,
+    /// Span of the name
+    pub name_span: Span,
+    /// Declared props
+    pub props: Vec<Field>,
+    /// Declared state variables
+    pub state: Vec<Field>,
+    /// Methods, in source order
+    pub methods: Vec<Method>,
+}
+
+/// Modifiers that may precede a method name
+const METHOD_MODIFIERS: &[&str] = &[
+    "pub",
+    "fn",
+    "async",
+    "function",
+    "static",
+    "public",
+    "private",
+    "protected",
+];
+
+impl Component {
+    /// Parse the `component Name { ... }` declaration in a script section
+    pub fn parse(text: &str, span: Span) -> Option<Self> {
+        let tokens = tokenize(&text[span.start..span.end], span.start);
+        let start = tokens.windows(3).position(|w| {
+            w[0].is_ident("component") && w[1].kind == TokenKind::Ident && w[2].is_punct("{")
+        })?;
+        let end = matching(&tokens, start + 2);
+
+        let mut component = Component {
+            name: tokens[start + 1].text.clone(),
+            name_span: tokens[start + 1].span,
+            props: vec![],
+            state: vec![],
+            methods: vec![],
+        };
+
+        let mut i = start + 3;
+        while i < end {
+            let token = &tokens[i];
+            let next = tokens.get(i + 1);
+
+            if (token.is_ident("props") || token.is_ident("state"))
+                && next.is_some_and(|t| t.is_punct("{"))
+            {
+                let close = matching(&tokens, i + 1);
+                let fields = parse_fields(text, &tokens[i + 2..close]);
+                if token.is_ident("props") {
+                    component.props.extend(fields);
+                } else {
+                    component.state.extend(fields);
+                }
+                i = close + 1;
+            } else if token.kind == TokenKind::Ident {
+                match parse_method(text, &tokens, i, end) {
+                    Some((method, after)) => {
+                        component.methods.push(method);
+                        i = after;
+                    }
+                    None => i += 1,
+                }
+            } else if token.is_punct("{") {
+                i = matching(&tokens, i) + 1;
+            } else {
+                i += 1;
+            }
+        }
+
+        Some(component)
+    }
+
+    /// Find a prop by name
+    pub fn prop(&self, name: &str) -> Option<&Field> {
+        self.props.iter().find(|f| f.name == name)
+    }
+
+    /// Find a state variable by name
+    pub fn state_variable(&self, name: &str) -> Option<&Field> {
+        self.state.iter().find(|f| f.name == name)
+    }
+
+    /// Find a method by name
+    pub fn method(&self, name: &str) -> Option<&Method> {
+        self.methods.iter().find(|m| m.name == name)
+    }
+
+    /// Names of all props, state variables and methods with their kinds
+    pub fn members(&self) -> impl Iterator<Item = (&str, MemberKind)> {
+        let props = self
+            .props
+            .iter()
+            .map(|f| (f.name.as_str(), MemberKind::Prop));
+        let state = self
+            .state
+            .iter()
+            .map(|f| (f.name.as_str(), MemberKind::State));
+        let methods = self
+            .methods
+            .iter()
+            .map(|m| (m.name.as_str(), MemberKind::Method));
+        props.chain(state).chain(methods)
+    }
+
+    /// Kind of the member with the given name
+    pub fn member(&self, name: &str) -> Option<MemberKind> {
+        self.members()
+            .find(|(n, _)| *n == name)
+            .map(|(_, kind)| kind)
+    }
+}
+
+/// Parse the declarations inside a `props { ... }` or `state { ... }` block
+fn parse_fields(text: &str, tokens: &[Token]) -> Vec<Field> {
+    let mut fields = vec![];
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let name = &tokens[i];
+        // Skip anything that isn't a name, including Rust visibility modifiers
+        if name.kind != TokenKind::Ident
+            || (name.text == "pub"
+                && tokens
+                    .get(i + 1)
+                    .is_some_and(|t| t.kind == TokenKind::Ident))
+        {
+            i += 1;
+            continue;
+        }
+        i += 1;
+
+        let optional = tokens.get(i).is_some_and(|t| t.is_punct("?"));
+        if optional {
+            i += 1;
+        }
+
+        let mut type_annotation = None;
+        if tokens.get(i).is_some_and(|t| t.is_punct(":")) {
+            let end = scan_until(tokens, i + 1, &["=", ";", ","], true);
+            type_annotation = fragment(text, &tokens[i + 1..end]);
+            i = end;
+        }
+
+        let mut initial_value = None;
+        if tokens.get(i).is_some_and(|t| t.is_punct("=")) {
+            let end = scan_until(tokens, i + 1, &[";", ","], false);
+            initial_value = fragment(text, &tokens[i + 1..end]);
+            i = end;
+        }
+
+        let mut end = tokens[i - 1].span.end;
+        if let Some(separator) = tokens.get(i).filter(|t| t.is_punct(";") || t.is_punct(",")) {
+            end = separator.span.end;
+            i += 1;
+        }
+
+        fields.push(Field {
+            name: name.text.clone(),
+            name_span: name.span,
+            optional,
+            type_annotation,
+            initial_value,
+            span: Span::new(name.span.start, end),
+        });
+    }
+
+    fields
+}
+
+/// Parse a method starting at `start` (which may be a modifier), returning it and the index after it
+fn parse_method(text: &str, tokens: &[Token], start: usize, end: usize) -> Option<(Method, usize)> {
+    let mut i = start;
+    while i < end && METHOD_MODIFIERS.contains(&tokens[i].text.as_str()) {
+        i += 1;
+    }
+    let name = tokens.get(i).filter(|t| t.kind == TokenKind::Ident)?;
+    if !tokens.get(i + 1)?.is_punct("(") {
+        return None;
+    }
+
+    let close_params = matching(tokens, i + 1);
+    let params_span = Span::new(
+        tokens[i + 1].span.end,
+        tokens
+            .get(close_params)
+            .map_or(tokens[i + 1].span.end, |t| t.span.start),
+    );
+    let params = parse_params(text, &tokens[i + 2..close_params.min(end)]);
+
+    let mut j = close_params + 1;
+    let mut return_type = None;
+    if tokens
+        .get(j)
+        .is_some_and(|t| t.is_punct(":") || t.is_punct("->"))
+    {
+        let body = (j + 1..end).find(|&k| tokens[k].is_punct("{"))?;
+        return_type = fragment(text, &tokens[j + 1..body]);
+        j = body;
+    }
+    if !tokens.get(j)?.is_punct("{") {
+        return None;
+    }
+
+    let close_body = matching(tokens, j);
+    let body_end = tokens
+        .get(close_body)
+        .map_or(tokens[j].span.end, |t| t.span.start);
+
+    Some((
+        Method {
+            name: name.text.clone(),
+            name_span: name.span,
+            params,
+            params_span,
+            return_type,
+            body: Span::new(tokens[j].span.end, body_end),
+            span: Span::new(
+                tokens[start].span.start,
+                tokens.get(close_body).map_or(body_end, |t| t.span.end),
+            ),
+            public: tokens[start..i]
+                .iter()
+                .any(|t| t.text == "pub" || t.text == "public"),
+        },
+        close_body + 1,
+    ))
+}
+
+/// Parse a comma-separated parameter list
+fn parse_params(text: &str, tokens: &[Token]) -> Vec<Param> {
+    let mut params = vec![];
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let end = scan_until(tokens, i, &[","], true);
+        let param = &tokens[i..end];
+        // Skip Rust receivers and binding modifiers (`&self`, `&mut self`, `mut x`)
+        let name = param
+            .iter()
+            .find(|t| t.kind == TokenKind::Ident && t.text != "mut")
+            .filter(|t| t.text != "self");
+
+        if let Some(name) = name {
+            let default = scan_until(param, 0, &["="], true);
+            let type_annotation = param[..default]
+                .iter()
+                .position(|t| t.is_punct(":"))
+                .and_then(|colon| fragment(text, &param[colon + 1..default]));
+            params.push(Param {
+                name: name.text.clone(),
+                name_span: name.span,
+                type_annotation,
+                optional: default < param.len() || param.iter().any(|t| t.is_punct("?")),
+                variadic: param.first().is_some_and(|t| t.is_punct("...")),
+            });
+        }
+        i = end + 1;
+    }
+
+    params
+}
+
+/// Index of the first token at nesting depth 0 matching one of `stops`, or `tokens.len()`
+///
+/// Angle brackets only nest in types, where `Vec<(i32, i32)>` must not stop at the comma.
+fn scan_until(tokens: &[Token], start: usize, stops: &[&str], angles: bool) -> usize {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        if token.kind != TokenKind::Punct {
+            continue;
+        }
+        match token.text.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth = depth.saturating_sub(1),
+            "<" if angles => depth += 1,
+            ">" if angles => depth = depth.saturating_sub(1),
+            ">>" if angles => depth = depth.saturating_sub(2),
+            text if depth == 0 && stops.contains(&text) => return i,
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+/// Index of the bracket closing the one at `open`, or `tokens.len()` if it is unclosed
+fn matching(tokens: &[Token], open: usize) -> usize {
+    let (opener, closer) = match tokens[open].text.as_str() {
+        "(" => ("(", ")"),
+        "[" => ("[", "]"),
+        _ => ("{", "}"),
+    };
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if token.is_punct(opener) {
+            depth += 1;
+        } else if token.is_punct(closer) {
+            depth -= 1;
+            if depth == 0 {
+                return i;
+            }
+        }
+    }
+    tokens.len()
+}
+
+/// Source text covered by a run of tokens
+fn fragment(text: &str, tokens: &[Token]) -> Option<Fragment> {
+    let span = Span::new(tokens.first()?.span.start, tokens.last()?.span.end);
+    Some(Fragment {
+        text: text[span.start..span.end].to_string(),
+        span,
+    })
+}
@@ -0,0 +1,382 @@
+// Template expressions
+// Collects the expressions embedded in a template (`{{ }}` interpolations, `:attr` bindings,
+// `@event` handlers and `v-` directives) and finds the free identifiers they reference,
+// taking `v-for` loop variables and arrow function parameters into account.
+
+use super::script::{access, substitutions, tokenize, Access, Token, TokenKind};
+use super::template::{Attribute, Element, Node};
+use super::{SourceFile, Span};
+
+/// Where an expression appears in the template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpressionKind {
+    /// `{{ expression }}`
+    Interpolation,
+    /// `:attr="expression"` or `v-bind:attr="expression"`
+    Binding,
+    /// `@event="handler"` or `v-on:event="handler"`
+    Event,
+    /// Other directives such as `v-if="expression"`; for `v-for` only the iterated expression
+    Directive,
+}
+
+/// An expression in the template
+#[derive(Debug, Clone)]
+pub struct Expression<'a> {
+    /// Where the expression appears
+    pub kind: ExpressionKind,
+    /// Expression text
+    pub text: &'a str,
+    /// Span of the expression text
+    pub span: Span,
+    /// Element the expression belongs to (the parent element for interpolations)
+    pub element: Option<&'a Element>,
+    /// Attribute holding the expression, if any
+    pub attribute: Option<&'a Attribute>,
+    /// Loop variables in scope
+    pub locals: Vec<String>,
+}
+
+/// A free identifier used in an expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// Identifier name
+    pub name: String,
+    /// Span of the identifier
+    pub span: Span,
+    /// Whether the expression reads or assigns the identifier
+    pub access: Access,
+    /// Whether the identifier is a shorthand property, such as `open` in `{ open, active: isActive }`
+    pub shorthand: bool,
+}
+
+/// The method or callback an event directive calls, e.g. `save` in `@click="save"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Handler {
+    /// Handler name
+    pub name: String,
+    /// Span of the name
+    pub span: Span,
+    /// Number of arguments for calls like `select(item)`; `None` for a bare reference,
+    /// which is called with the event
+    pub arguments: Option<usize>,
+}
+
+/// Keywords and literals that are never references
+const KEYWORDS: &[&str] = &[
+    "true",
+    "false",
+    "null",
+    "undefined",
+    "this",
+    "typeof",
+    "instanceof",
+    "new",
+    "in",
+    "of",
+    "void",
+    "delete",
+    "let",
+    "const",
+    "var",
+    "return",
+    "NaN",
+    "Infinity",
+];
+
+/// Globals available to every template expression
+const GLOBALS: &[&str] = &[
+    "$event",
+    "event",
+    "Math",
+    "Date",
+    "JSON",
+    "console",
+    "Number",
+    "String",
+    "Boolean",
+    "Array",
+    "Object",
+    "parseInt",
+    "parseFloat",
+    "isNaN",
+    "isFinite",
+    "encodeURIComponent",
+    "decodeURIComponent",
+    "Intl",
+    "window",
+    "document",
+];
+
+impl Expression<'_> {
+    /// Free identifiers referenced by the expression
+    ///
+    /// Member accesses (`user.name`) only reference their root (`user`), `this.name`
+    /// references `name`, and object literal keys, loop variables, arrow function
+    /// parameters, keywords and well-known globals are skipped.
+    pub fn references(&self) -> Vec<Reference> {
+        let tokens = tokenize(self.text, self.span.start);
+        let mut references = vec![];
+        self.collect_references(&tokens, &arrow_params(&tokens), &mut references);
+        references
+    }
+
+    /// Collect the references in `tokens`, then in the `${...}` substitutions of their
+    /// template literals, where the arrow function parameters in `params` are still in scope
+    fn collect_references(
+        &self,
+        tokens: &[Token],
+        params: &[&str],
+        references: &mut Vec<Reference>,
+    ) {
+        for (i, token) in tokens.iter().enumerate() {
+            if token.kind != TokenKind::Ident {
+                continue;
+            }
+            let prev = i.checked_sub(1).map(|p| &tokens[p]);
+            let next = tokens.get(i + 1);
+
+            let is_member = prev.is_some_and(|p| p.is_punct(".") || p.is_punct("?."));
+            let via_this = is_member && i >= 2 && tokens[i - 2].is_ident("this");
+            let is_key = next.is_some_and(|n| n.is_punct(":"))
+                && prev.is_some_and(|p| p.is_punct("{") || p.is_punct(","));
+
+            if (is_member && !via_this)
+                || is_key
+                || KEYWORDS.contains(&token.text.as_str())
+                || GLOBALS.contains(&token.text.as_str())
+                || params.contains(&token.text.as_str())
+                || (!via_this && self.locals.contains(&token.text))
+            {
+                continue;
+            }
+
+            references.push(Reference {
+                name: token.text.clone(),
+                span: token.span,
+                access: access(tokens, i),
+                shorthand: is_shorthand_property(tokens, i),
+            });
+        }
+
+        for token in tokens {
+            for part in substitutions(token) {
+                let mut params = params.to_vec();
+                params.extend(arrow_params(&part));
+                self.collect_references(&part, &params, references);
+            }
+        }
+    }
+
+    /// Handler of an event directive that names a method or callback directly
+    ///
+    /// Recognises `handler`, `this.handler` and `handler(args)`; inline statements such as
+    /// `count++` or arrow functions have no handler.
+    pub fn handler(&self) -> Option<Handler> {
+        if self.kind != ExpressionKind::Event {
+            return None;
+        }
+        let mut tokens = tokenize(self.text, self.span.start);
+        if tokens.last().is_some_and(|t| t.is_punct(";")) {
+            tokens.pop();
+        }
+        let start = if tokens.first()?.is_ident("this") && tokens.get(1)?.is_punct(".") {
+            2
+        } else {
+            0
+        };
+        let name = tokens
+            .get(start)
+            .filter(|t| t.kind == TokenKind::Ident && !self.locals.contains(&t.text))?;
+        let rest = &tokens[start + 1..];
+
+        let arguments = match rest {
+            [] => None,
+            [open, .., close] if open.is_punct("(") && close.is_punct(")") => {
+                let inner = &rest[1..rest.len() - 1];
+                let mut depth = 0usize;
+                let mut count = usize::from(!inner.is_empty());
+                for token in inner {
+                    match token.text.as_str() {
+                        "(" | "[" | "{" => depth += 1,
+                        ")" | "]" | "}" => depth = depth.checked_sub(1)?,
+                        "," if depth == 0 => count += 1,
+                        _ => {}
+                    }
+                }
+                // Unbalanced inner brackets mean something like `a(b) + c(d)`, not a plain call
+                if depth != 0 {
+                    return None;
+                }
+                Some(count)
+            }
+            _ => return None,
+        };
+
+        Some(Handler {
+            name: name.text.clone(),
+            span: name.span,
+            arguments,
+        })
+    }
+}
+
+/// Collect every expression in the file's template
+pub fn expressions(source: &SourceFile) -> Vec<Expression<'_>> {
+    let mut expressions = vec![];
+
+    source.template().visit(&mut |node, ancestors| {
+        let locals = |elements: &[&Element]| -> Vec<String> {
+            elements
+                .iter()
+                .filter_map(|e| e.attribute("v-for"))
+                .flat_map(|a| loop_variables(source, a))
+                .collect()
+        };
+
+        match node {
+            Node::Interpolation { span, .. } => {
+                let inner = Span::new(
+                    span.start + 2,
+                    if source.slice(*span).ends_with("}}") {
+                        span.end - 2
+                    } else {
+                        span.end
+                    },
+                );
+                expressions.push(Expression {
+                    kind: ExpressionKind::Interpolation,
+                    text: source.slice(inner),
+                    span: inner,
+                    element: ancestors.last().copied(),
+                    attribute: None,
+                    locals: locals(ancestors),
+                });
+            }
+            Node::Element(element) => {
+                let mut in_scope = ancestors.to_vec();
+                in_scope.push(element);
+
+                for attribute in &element.attributes {
+                    let Some(value_span) = attribute.value_span else {
+                        continue;
+                    };
+                    let name = attribute.name.as_str();
+
+                    let (kind, span, locals) = if name == "v-for" {
+                        let Some(span) = loop_iterable(source, attribute) else {
+                            continue;
+                        };
+                        (ExpressionKind::Directive, span, locals(ancestors))
+                    } else if name.starts_with('@') || name.starts_with("v-on:") {
+                        (ExpressionKind::Event, value_span, locals(&in_scope))
+                    } else if name.starts_with(':') || name.starts_with("v-bind:") {
+                        (ExpressionKind::Binding, value_span, locals(&in_scope))
+                    } else if name.starts_with("v-") {
+                        (ExpressionKind::Directive, value_span, locals(&in_scope))
+                    } else {
+                        continue;
+                    };
+
+                    expressions.push(Expression {
+                        kind,
+                        text: source.slice(span),
+                        span,
+                        element: Some(element),
+                        attribute: Some(attribute),
+                        locals,
+                    });
+                }
+            }
+            _ => {}
+        }
+    });
+
+    expressions
+}
+
+/// Split a `v-for` value into its variable tokens and the iterated expression tokens
+fn split_loop(source: &SourceFile, attribute: &Attribute) -> Option<(Vec<Token>, Vec<Token>)> {
+    let span = attribute.value_span?;
+    let tokens = tokenize(source.slice(span), span.start);
+    let split = tokens
+        .iter()
+        .position(|t| t.is_ident("in") || t.is_ident("of"))?;
+    let iterable = tokens[split + 1..].to_vec();
+    let mut variables = tokens;
+    variables.truncate(split);
+    Some((variables, iterable))
+}
+
+/// Variables introduced by a `v-for` directive, e.g. `item` and `index` in `(item, index) in items`
+fn loop_variables(source: &SourceFile, attribute: &Attribute) -> Vec<String> {
+    split_loop(source, attribute)
+        .map(|(variables, _)| {
+            variables
+                .into_iter()
+                .filter(|t| t.kind == TokenKind::Ident)
+                .map(|t| t.text)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Span of the iterated expression in a `v-for` directive
+fn loop_iterable(source: &SourceFile, attribute: &Attribute) -> Option<Span> {
+    let (_, iterable) = split_loop(source, attribute)?;
+    Some(Span::new(
+        iterable.first()?.span.start,
+        iterable.last()?.span.end,
+    ))
+}
+
+/// Parameters of arrow functions in an expression (`x => ...`, `(a, b) => ...`)
+fn arrow_params(tokens: &[Token]) -> Vec<&str> {
+    let mut params = vec![];
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_punct("=>") || i == 0 {
+            continue;
+        }
+        let prev = &tokens[i - 1];
+        if prev.kind == TokenKind::Ident {
+            params.push(prev.text.as_str());
+        } else if prev.is_punct(")") {
+            let open = tokens[..i - 1].iter().rposition(|t| t.is_punct("("));
+            if let Some(open) = open {
+                params.extend(
+                    tokens[open + 1..i - 1]
+                        .iter()
+                        .filter(|t| t.kind == TokenKind::Ident)
+                        .map(|t| t.text.as_str()),
+                );
+            }
+        }
+    }
+    params
+}
+
+/// Whether the identifier at `i` is a shorthand property in an object literal
+fn is_shorthand_property(tokens: &[Token], i: usize) -> bool {
+    let separated = |token: Option<&Token>, puncts: [&str; 2]| {
+        token.is_some_and(|t| puncts.iter().any(|p| t.is_punct(p)))
+    };
+    if !separated(i.checked_sub(1).map(|p| &tokens[p]), ["{", ","])
+        || !separated(tokens.get(i + 1), [",", "}"])
+    {
+        return false;
+    }
+
+    // The innermost bracket around the identifier must be a brace, not a call or an array
+    let mut depth = 0usize;
+    for token in tokens[..i].iter().rev() {
+        if token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            depth += 1;
+        } else if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") {
+            if depth == 0 {
+                return token.is_punct("{");
+            }
+            depth -= 1;
+        }
+    }
+    false
+}
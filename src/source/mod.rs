@@ -0,0 +1,247 @@
+// Source model for .orbit files
+// Splits a file into its sections and maps byte offsets back to line/column positions,
+// so rules can report issues at the exact location they were found
+
+pub mod component;
+pub mod expression;
+pub mod script;
+pub mod style;
+pub mod template;
+pub mod types;
+pub mod usage;
+
+use component::Component;
+use serde::{Deserialize, Serialize};
+use style::Stylesheet;
+use template::Template;
+
+/// A byte range in the original file content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Span {
+    /// Byte offset of the first character
+    pub start: usize,
+    /// Byte offset one past the last character
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+/// Kind of a top-level section in an .orbit file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionKind {
+    /// `<template>` markup
+    Template,
+    /// `<script>` or `<code lang="rust">` component definition
+    Script,
+    /// `<style>` block
+    Style,
+    /// `<metadata>` block with component settings such as `<renderer>` or `<page>`
+    Metadata,
+}
+
+impl SectionKind {
+    /// Parse a section name as used in configuration files
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "template" => Some(SectionKind::Template),
+            "script" | "code" => Some(SectionKind::Script),
+            "style" => Some(SectionKind::Style),
+            "metadata" => Some(SectionKind::Metadata),
+            _ => None,
+        }
+    }
+}
+
+/// A top-level section of an .orbit file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Section {
+    /// Kind of section
+    pub kind: SectionKind,
+    /// Span of the content between the opening and closing tags
+    pub content: Span,
+}
+
+/// An .orbit file together with its sections and parsed template
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    text: String,
+    line_starts: Vec<usize>,
+    sections: Vec<Section>,
+    template: Template,
+    stylesheet: Stylesheet,
+    component: Option<Component>,
+}
+
+impl SourceFile {
+    /// Split the given file content into sections
+    pub fn new(text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        let mut sections = vec![];
+        let section_tags: [(SectionKind, &[(&str, &str)]); 4] = [
+            (SectionKind::Template, &[("<template", "</template>")]),
+            (
+                SectionKind::Script,
+                &[("<script", "</script>"), ("<code", "</code>")],
+            ),
+            (SectionKind::Style, &[("<style", "</style>")]),
+            (SectionKind::Metadata, &[("<metadata", "</metadata>")]),
+        ];
+
+        for (kind, tags) in section_tags {
+            for (open, close) in tags {
+                if let Some(section) = find_section(text, kind, open, close) {
+                    sections.push(section);
+                    break;
+                }
+            }
+        }
+        sections.sort_by_key(|s| s.content.start);
+
+        let template = sections
+            .iter()
+            .find(|s| s.kind == SectionKind::Template)
+            .map(|s| Template::parse(text, s.content))
+            .unwrap_or_default();
+
+        let stylesheet = sections
+            .iter()
+            .find(|s| s.kind == SectionKind::Style)
+            .map(|s| Stylesheet::parse(text, s.content))
+            .unwrap_or_default();
+
+        let component = sections
+            .iter()
+            .find(|s| s.kind == SectionKind::Script)
+            .and_then(|s| Component::parse(text, s.content));
+
+        Self {
+            text: text.to_string(),
+            line_starts,
+            sections,
+            template,
+            stylesheet,
+            component,
+        }
+    }
+
+    /// Full file content
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// All sections found in the file, in source order
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Find a section by kind
+    pub fn section(&self, kind: SectionKind) -> Option<&Section> {
+        self.sections.iter().find(|s| s.kind == kind)
+    }
+
+    /// Content of a section, if the file has one
+    pub fn section_text(&self, kind: SectionKind) -> Option<&str> {
+        self.section(kind).map(|s| self.slice(s.content))
+    }
+
+    /// Parsed template markup
+    pub fn template(&self) -> &Template {
+        &self.template
+    }
+
+    /// Parsed style rules
+    pub fn stylesheet(&self) -> &Stylesheet {
+        &self.stylesheet
+    }
+
+    /// Text of a `<name>` entry in the metadata section; empty for `<name />`
+    pub fn metadata(&self, name: &str) -> Option<String> {
+        let section = self.section(SectionKind::Metadata)?;
+        let entries = Template::parse(&self.text, section.content);
+        let entry = entries.elements().into_iter().find(|e| e.tag == name)?;
+        let text: String = entry
+            .children
+            .iter()
+            .filter_map(|node| match node {
+                template::Node::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        Some(text.trim().to_string())
+    }
+
+    /// Component declared in the script section, if one could be found
+    pub fn component(&self) -> Option<&Component> {
+        self.component.as_ref()
+    }
+
+    /// Text covered by a span
+    pub fn slice(&self, span: Span) -> &str {
+        &self.text[span.start..span.end]
+    }
+
+    /// Span to delete when removing the code in `span`
+    ///
+    /// If nothing else is on the lines covered by `span`, the whole lines are removed so no
+    /// blank line is left behind.
+    pub fn removal_span(&self, span: Span) -> Span {
+        let before = &self.text[..span.start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let after = &self.text[span.end..];
+        let line_end = after
+            .find('\n')
+            .map_or(self.text.len(), |i| span.end + i + 1);
+
+        if before[line_start..].trim().is_empty() && self.text[span.end..line_end].trim().is_empty()
+        {
+            Span::new(line_start, line_end)
+        } else {
+            span
+        }
+    }
+
+    /// Convert a byte offset into a 1-based (line, column) position
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.text.len());
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next) => next - 1,
+        };
+        let column = self.text[self.line_starts[line]..offset].chars().count() + 1;
+        (line + 1, column)
+    }
+}
+
+/// Locate a section delimited by the given opening and closing tags
+fn find_section(text: &str, kind: SectionKind, open: &str, close: &str) -> Option<Section> {
+    let mut search_from = 0;
+    let tag_start = loop {
+        let candidate = search_from + text[search_from..].find(open)?;
+        // Make sure we matched the whole tag name (`<code` must not match `<codec`)
+        match text[candidate + open.len()..].chars().next() {
+            Some(c) if c == '>' || c.is_whitespace() => break candidate,
+            _ => search_from = candidate + open.len(),
+        }
+    };
+    let content_start = tag_start + text[tag_start..].find('>')? + 1;
+
+    // Templates may contain nested <template> tags, so match the last closing tag
+    let content_end = if kind == SectionKind::Template {
+        text.rfind(close)?
+    } else {
+        content_start + text[content_start..].find(close)?
+    };
+
+    (content_end >= content_start).then(|| Section {
+        kind,
+        content: Span::new(content_start, content_end),
+    })
+}
@@ -0,0 +1,230 @@
+// Tokenizer for the script section
+// Produces identifiers, literals and punctuation with source spans while skipping
+// comments, which is enough for rules that look for names rather than full semantics
+
+use super::Span;
+
+/// Kind of a script token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Identifier or keyword
+    Ident,
+    /// String or template literal, including quotes
+    Str,
+    /// Numeric literal
+    Number,
+    /// Operator or punctuation; multi-character operators form a single token
+    Punct,
+}
+
+/// A token in the script section
+#[derive(Debug, Clone)]
+pub struct Token {
+    /// Token kind
+    pub kind: TokenKind,
+    /// Token text as written in the source
+    pub text: String,
+    /// Span of the token in the file
+    pub span: Span,
+}
+
+impl Token {
+    /// Whether this token is the identifier `name`
+    pub fn is_ident(&self, name: &str) -> bool {
+        self.kind == TokenKind::Ident && self.text == name
+    }
+
+    /// Whether this token is the punctuation `punct`
+    pub fn is_punct(&self, punct: &str) -> bool {
+        self.kind == TokenKind::Punct && self.text == punct
+    }
+}
+
+/// How an identifier is used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// The value is only read
+    Read,
+    /// The value is replaced (`x = 1`)
+    Write,
+    /// The value is read and replaced (`x += 1`, `x++`)
+    ReadWrite,
+}
+
+impl Access {
+    /// Whether the value is read
+    pub fn reads(self) -> bool {
+        self != Access::Write
+    }
+
+    /// Whether the value is replaced
+    pub fn writes(self) -> bool {
+        self != Access::Read
+    }
+}
+
+/// Compound assignment operators
+const COMPOUND_ASSIGNMENTS: &[&str] = &[
+    "+=", "-=", "*=", "/=", "%=", "**=", "<<=", ">>=", ">>>=", "&=", "|=", "^=", "&&=", "||=",
+    "??=",
+];
+
+/// How the identifier at `index` is used, looking through a `this.` or `self.` prefix
+pub fn access(tokens: &[Token], index: usize) -> Access {
+    let has_receiver = index >= 2
+        && tokens[index - 1].is_punct(".")
+        && (tokens[index - 2].is_ident("this") || tokens[index - 2].is_ident("self"));
+    let start = if has_receiver { index - 2 } else { index };
+    let prev = start.checked_sub(1).map(|i| &tokens[i]);
+
+    match tokens.get(index + 1) {
+        Some(next) if next.is_punct("=") => Access::Write,
+        Some(next)
+            if next.is_punct("++")
+                || next.is_punct("--")
+                || COMPOUND_ASSIGNMENTS.contains(&next.text.as_str()) =>
+        {
+            Access::ReadWrite
+        }
+        _ if prev.is_some_and(|p| p.is_punct("++") || p.is_punct("--")) => Access::ReadWrite,
+        _ => Access::Read,
+    }
+}
+
+/// Operators recognised as a single token, longest first
+const OPERATORS: &[&str] = &[
+    ">>>=", "===", "!==", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=", "...", "=>", "==", "!=",
+    "<=", ">=", "&&", "||", "??", "?.", "++", "--", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=",
+    "**", "<<", ">>", "::", "->",
+];
+
+/// Tokenize `text`, reporting spans relative to `offset`
+pub fn tokenize(text: &str, offset: usize) -> Vec<Token> {
+    scan(text, offset, false).0
+}
+
+/// Tokenize `text`, stopping at an unmatched `}` when `substitution` is set, and return the
+/// tokens with the length scanned (including that `}`)
+fn scan(text: &str, offset: usize, substitution: bool) -> (Vec<Token>, usize) {
+    let mut tokens = vec![];
+    let mut chars = text.char_indices().peekable();
+    let mut depth = 0usize;
+
+    while let Some(&(start, c)) = chars.peek() {
+        let rest = &text[start..];
+
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        // Comments
+        if rest.starts_with("//") {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            skip_to(&mut chars, start + len);
+            continue;
+        }
+        if let Some(body) = rest.strip_prefix("/*") {
+            let len = body.find("*/").map_or(rest.len(), |i| i + 4);
+            skip_to(&mut chars, start + len);
+            continue;
+        }
+
+        let (kind, len) = if c == '`' {
+            (TokenKind::Str, template(rest, offset + start).0)
+        } else if c == '"' || c == '\'' {
+            (TokenKind::Str, string_len(rest, c))
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '.' || ch == '_'))
+                .unwrap_or(rest.len());
+            (TokenKind::Number, len)
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let len = rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_' || ch == '$'))
+                .unwrap_or(rest.len());
+            (TokenKind::Ident, len)
+        } else {
+            let len = OPERATORS
+                .iter()
+                .find(|op| rest.starts_with(*op))
+                .map_or(c.len_utf8(), |op| op.len());
+            (TokenKind::Punct, len)
+        };
+
+        if substitution && kind == TokenKind::Punct {
+            match &rest[..len] {
+                "{" => depth += 1,
+                "}" if depth == 0 => return (tokens, start + len),
+                "}" => depth -= 1,
+                _ => {}
+            }
+        }
+
+        tokens.push(Token {
+            kind,
+            text: rest[..len].to_string(),
+            span: Span::new(offset + start, offset + start + len),
+        });
+        skip_to(&mut chars, start + len);
+    }
+
+    (tokens, text.len())
+}
+
+/// Length of a string literal starting with `quote`, honouring escapes
+fn string_len(rest: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return i + c.len_utf8();
+        }
+    }
+    rest.len()
+}
+
+/// Tokens of each `${...}` substitution in a template literal token
+///
+/// The literal stays a single [`TokenKind::Str`] token; its substitutions are tokenized
+/// separately so that names used in them can be found without mixing them into the
+/// surrounding expression.
+pub fn substitutions(token: &Token) -> Vec<Vec<Token>> {
+    if token.kind == TokenKind::Str && token.text.starts_with('`') {
+        template(&token.text, token.span.start).1
+    } else {
+        vec![]
+    }
+}
+
+/// Length of a template literal starting with a backtick and the tokens of its substitutions,
+/// which may themselves contain braces, strings and nested template literals
+fn template(rest: &str, offset: usize) -> (usize, Vec<Vec<Token>>) {
+    let mut parts = vec![];
+    let mut escaped = false;
+    let mut i = 1;
+
+    while let Some(c) = rest[i..].chars().next() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '`' {
+            return (i + 1, parts);
+        } else if rest[i..].starts_with("${") {
+            let (tokens, len) = scan(&rest[i + 2..], offset + i + 2, true);
+            parts.push(tokens);
+            i += 2 + len;
+            continue;
+        }
+        i += c.len_utf8();
+    }
+    (rest.len(), parts)
+}
+
+fn skip_to(chars: &mut std::iter::Peekable<std::str::CharIndices>, end: usize) {
+    while chars.next_if(|&(i, _)| i < end).is_some() {}
+}
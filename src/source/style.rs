@@ -0,0 +1,838 @@
+// Lightweight stylesheet parser
+// Splits the <style> section into rules, selectors and declarations with source spans.
+// Nested rules (CSS nesting and SCSS) are flattened onto their parent selectors, comments
+// are skipped and unbalanced braces close at the end of the section, so rules can still
+// run on partially broken styles.
+
+use super::template::Element;
+use super::Span;
+
+/// At-rules whose blocks contain style rules; others such as `@keyframes` or `@font-face` are skipped
+const GROUPING_AT_RULES: &[&str] = &[
+    "media",
+    "supports",
+    "container",
+    "layer",
+    "document",
+    "scope",
+];
+
+/// Pseudo-elements that may be written with a single colon
+const LEGACY_PSEUDO_ELEMENTS: &[&str] = &["before", "after", "first-line", "first-letter"];
+
+/// Pseudo-classes whose argument is a selector list
+const SELECTOR_PSEUDO_CLASSES: &[&str] = &[
+    "not", "is", "where", "matches", "has", "deep", "global", "slotted",
+];
+
+/// Parsed `<style>` section
+#[derive(Debug, Clone, Default)]
+pub struct Stylesheet {
+    /// Style rules in source order; nested rules follow their parent
+    pub rules: Vec<StyleRule>,
+}
+
+/// A style rule such as `.card { color: red }`
+#[derive(Debug, Clone)]
+pub struct StyleRule {
+    /// Selectors of the rule's selector list, resolved against any parent rules
+    pub selectors: Vec<Selector>,
+    /// Declarations in source order, without those of nested rules
+    pub declarations: Vec<Declaration>,
+    /// Span of the selector list
+    pub span: Span,
+    /// Preludes of the enclosing at-rules, outermost first (`@media (max-width: 600px)`)
+    pub conditions: Vec<String>,
+    /// Whether the rule is nested inside another style rule
+    pub nested: bool,
+}
+
+/// A complex selector such as `.card > h2`
+#[derive(Debug, Clone)]
+pub struct Selector {
+    /// Selector text as written; for nested rules, without the parent selector
+    pub text: String,
+    /// Span of the selector text
+    pub span: Span,
+    /// Compound selectors from left to right, including those inherited from parent rules
+    pub compounds: Vec<Compound>,
+}
+
+/// How a compound selector is joined to the one before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Combinator {
+    /// Whitespace
+    #[default]
+    Descendant,
+    /// `>`
+    Child,
+    /// `+`
+    NextSibling,
+    /// `~`
+    SubsequentSibling,
+}
+
+/// A compound selector such as `button.primary:hover`
+#[derive(Debug, Clone, Default)]
+pub struct Compound {
+    /// Combinator joining this compound to the previous one; `Descendant` for the first
+    pub combinator: Combinator,
+    /// Element name, if one was given
+    pub tag: Option<String>,
+    /// Whether the compound contains `*`
+    pub universal: bool,
+    /// `#id` selectors
+    pub ids: Vec<SelectorName>,
+    /// `.class` selectors
+    pub classes: Vec<SelectorName>,
+    /// `[attribute]` selectors
+    pub attributes: Vec<AttributeSelector>,
+    /// Pseudo-classes such as `:hover` or `:not(.active)`
+    pub pseudo_classes: Vec<PseudoClass>,
+    /// Pseudo-element such as `::before`
+    pub pseudo_element: Option<String>,
+}
+
+/// A class or id name in a selector
+#[derive(Debug, Clone)]
+pub struct SelectorName {
+    /// Name without escapes or the `.`/`#` prefix
+    pub name: String,
+    /// Span of the name including its prefix
+    pub span: Span,
+}
+
+/// An attribute selector such as `[type="text"]`
+#[derive(Debug, Clone)]
+pub struct AttributeSelector {
+    /// Attribute name
+    pub name: String,
+    /// Operator (`=`, `~=`, `^=`, ...) if a value is compared
+    pub operator: Option<String>,
+    /// Value without quotes
+    pub value: Option<String>,
+}
+
+/// A pseudo-class such as `:hover` or `:not(.active)`
+#[derive(Debug, Clone)]
+pub struct PseudoClass {
+    /// Lowercase name without the colon
+    pub name: String,
+    /// Argument text inside the parentheses
+    pub argument: Option<String>,
+    /// Parsed argument for pseudo-classes that take a selector list (`:not`, `:is`, ...)
+    pub selectors: Vec<Selector>,
+}
+
+/// A declaration such as `color: red !important`
+#[derive(Debug, Clone)]
+pub struct Declaration {
+    /// Property name as written
+    pub property: String,
+    /// Value without `!important`
+    pub value: String,
+    /// Whether the declaration is marked `!important`
+    pub important: bool,
+    /// Span of the declaration without the trailing semicolon
+    pub span: Span,
+    /// Span of the property name
+    pub property_span: Span,
+    /// Span of the value
+    pub value_span: Span,
+}
+
+impl Stylesheet {
+    /// Parse the styles covered by `span` in `text`
+    pub fn parse(text: &str, span: Span) -> Self {
+        let content = strip_comments(&text[span.start..span.end]);
+        let mut parser = StyleParser {
+            text: &content,
+            offset: span.start,
+            rules: vec![],
+        };
+        parser.block(0, content.len(), &[], &[]);
+        Self {
+            rules: parser.rules,
+        }
+    }
+}
+
+/// Parse the declarations of an inline `style` attribute value covered by `span` in `text`
+pub fn parse_declarations(text: &str, span: Span) -> Vec<Declaration> {
+    let content = strip_comments(&text[span.start..span.end]);
+    let mut parser = StyleParser {
+        text: &content,
+        offset: span.start,
+        rules: vec![],
+    };
+    parser.block(0, content.len(), &[], &[])
+}
+
+impl Selector {
+    /// Specificity as (ids, classes, elements)
+    pub fn specificity(&self) -> (usize, usize, usize) {
+        self.compounds
+            .iter()
+            .map(Compound::specificity)
+            .fold((0, 0, 0), add_specificity)
+    }
+
+    /// Whether the selector matches `element`, given its ancestors from the root down
+    ///
+    /// Only static `class`, `id` and attribute values are known. Siblings aren't tracked, so
+    /// `+` and `~` assume the sibling matches, and pseudo-classes other than `:not`, `:is`
+    /// and `:where` are assumed to apply.
+    pub fn matches(&self, element: &Element, ancestors: &[&Element]) -> bool {
+        matches_compounds(&self.compounds, element, ancestors)
+    }
+
+    /// Class names used anywhere in the selector, including pseudo-class arguments
+    pub fn class_names(&self) -> Vec<&SelectorName> {
+        let mut names = vec![];
+        for compound in &self.compounds {
+            names.extend(&compound.classes);
+            for pseudo in &compound.pseudo_classes {
+                for selector in &pseudo.selectors {
+                    names.extend(selector.class_names());
+                }
+            }
+        }
+        names
+    }
+
+    /// Rightmost compound, which selects the element the rule applies to
+    pub fn subject(&self) -> Option<&Compound> {
+        self.compounds.last()
+    }
+}
+
+impl Compound {
+    /// Whether the compound has no simple selectors
+    pub fn is_empty(&self) -> bool {
+        self.tag.is_none()
+            && !self.universal
+            && self.ids.is_empty()
+            && self.classes.is_empty()
+            && self.attributes.is_empty()
+            && self.pseudo_classes.is_empty()
+            && self.pseudo_element.is_none()
+    }
+
+    /// Specificity as (ids, classes, elements)
+    pub fn specificity(&self) -> (usize, usize, usize) {
+        let mut specificity = (
+            self.ids.len(),
+            self.classes.len() + self.attributes.len(),
+            usize::from(self.tag.is_some()) + usize::from(self.pseudo_element.is_some()),
+        );
+        for pseudo in &self.pseudo_classes {
+            match pseudo.name.as_str() {
+                "where" => {}
+                name if SELECTOR_PSEUDO_CLASSES.contains(&name) => {
+                    let argument = pseudo
+                        .selectors
+                        .iter()
+                        .map(Selector::specificity)
+                        .max()
+                        .unwrap_or_default();
+                    specificity = add_specificity(specificity, argument);
+                }
+                _ => specificity.1 += 1,
+            }
+        }
+        specificity
+    }
+
+    /// Whether the compound matches `element` on its own, ignoring combinators
+    pub fn matches(&self, element: &Element) -> bool {
+        if let Some(tag) = &self.tag {
+            if !tag.eq_ignore_ascii_case(&element.tag) {
+                return false;
+            }
+        }
+        let id = element.attribute("id").and_then(|a| a.value.as_deref());
+        if !self.ids.iter().all(|name| id == Some(name.name.as_str())) {
+            return false;
+        }
+        if !self
+            .classes
+            .iter()
+            .all(|name| element.classes().any(|class| class == name.name))
+        {
+            return false;
+        }
+        if !self.attributes.iter().all(|a| a.matches(element)) {
+            return false;
+        }
+        self.pseudo_classes.iter().all(|pseudo| {
+            // Arguments with combinators can't be checked against a single element
+            let compounds: Option<Vec<&Compound>> = pseudo
+                .selectors
+                .iter()
+                .map(|s| match s.compounds.as_slice() {
+                    [compound] => Some(compound),
+                    _ => None,
+                })
+                .collect();
+            match (pseudo.name.as_str(), compounds) {
+                ("not", Some(compounds)) => !compounds.iter().any(|c| c.matches(element)),
+                ("is" | "where" | "matches", Some(compounds)) => {
+                    compounds.iter().any(|c| c.matches(element))
+                }
+                _ => true,
+            }
+        })
+    }
+}
+
+impl AttributeSelector {
+    /// Whether `element` has the attribute with a matching static value
+    ///
+    /// A bound attribute (`:type`) counts as present, but its value is unknown.
+    pub fn matches(&self, element: &Element) -> bool {
+        let attribute = element.attributes.iter().find(|a| {
+            let name = a
+                .bound_name()
+                .or_else(|| a.name.strip_prefix("v-bind:"))
+                .unwrap_or(&a.name);
+            name.eq_ignore_ascii_case(&self.name)
+        });
+        let Some(attribute) = attribute else {
+            return false;
+        };
+        let (Some(operator), Some(expected)) = (&self.operator, &self.value) else {
+            return true;
+        };
+        if attribute.name != self.name {
+            return false;
+        }
+        let value = attribute.value.as_deref().unwrap_or("");
+        match operator.as_str() {
+            "=" => value == expected,
+            "~=" => value.split_whitespace().any(|word| word == expected),
+            "|=" => value == expected || value.starts_with(&format!("{expected}-")),
+            "^=" => !expected.is_empty() && value.starts_with(expected.as_str()),
+            "$=" => !expected.is_empty() && value.ends_with(expected.as_str()),
+            "*=" => !expected.is_empty() && value.contains(expected.as_str()),
+            _ => false,
+        }
+    }
+}
+
+fn add_specificity(a: (usize, usize, usize), b: (usize, usize, usize)) -> (usize, usize, usize) {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2)
+}
+
+/// Whether `compounds` match `element`, given its ancestors
+fn matches_compounds(compounds: &[Compound], element: &Element, ancestors: &[&Element]) -> bool {
+    let Some((last, rest)) = compounds.split_last() else {
+        return true;
+    };
+    last.matches(element) && matches_context(rest, last.combinator, ancestors)
+}
+
+/// Whether `compounds` match the context an element is joined to by `combinator`
+fn matches_context(compounds: &[Compound], combinator: Combinator, ancestors: &[&Element]) -> bool {
+    let Some((last, rest)) = compounds.split_last() else {
+        return true;
+    };
+    match combinator {
+        Combinator::Child => match ancestors.split_last() {
+            Some((parent, above)) => matches_compounds(compounds, parent, above),
+            None => false,
+        },
+        Combinator::Descendant => (0..ancestors.len())
+            .rev()
+            .any(|i| matches_compounds(compounds, ancestors[i], &ancestors[..i])),
+        // Siblings aren't tracked; assume the sibling matches and check the shared ancestors
+        Combinator::NextSibling | Combinator::SubsequentSibling => {
+            matches_context(rest, last.combinator, ancestors)
+        }
+    }
+}
+
+/// Replace comments with spaces, keeping byte offsets and line breaks
+fn strip_comments(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut output = bytes.to_vec();
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if let Some(q) = quote {
+            if byte == b'\\' {
+                i += 1;
+            } else if byte == q || byte == b'\n' {
+                quote = None;
+            }
+            i += 1;
+            continue;
+        }
+        let comment_end = match (byte, bytes.get(i + 1)) {
+            (b'/', Some(b'*')) => Some(text[i + 2..].find("*/").map_or(bytes.len(), |e| i + e + 4)),
+            // SCSS line comments; `//` inside parentheses is part of a url
+            (b'/', Some(b'/')) if depth == 0 => {
+                Some(text[i..].find('\n').map_or(bytes.len(), |e| i + e))
+            }
+            _ => None,
+        };
+        if let Some(end) = comment_end {
+            for byte in &mut output[i..end] {
+                if *byte != b'\n' {
+                    *byte = b' ';
+                }
+            }
+            i = end;
+            continue;
+        }
+        match byte {
+            b'"' | b'\'' => quote = Some(byte),
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        i += 1;
+    }
+    String::from_utf8(output).expect("comments are replaced whole, so the text stays valid UTF-8")
+}
+
+/// Index of the first of `delimiters` in `from..end` outside strings and brackets, or `end`
+///
+/// Braces always count as delimiters, so an unbalanced parenthesis can't swallow the rest
+/// of the stylesheet.
+fn find_top_level(text: &str, from: usize, end: usize, delimiters: &[u8]) -> usize {
+    let bytes = text.as_bytes();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut i = from;
+    while i < end {
+        let byte = bytes[i];
+        if let Some(q) = quote {
+            if byte == b'\\' {
+                i += 1;
+            } else if byte == q || byte == b'\n' {
+                quote = None;
+            }
+        } else if delimiters.contains(&byte) && (depth == 0 || byte == b'{' || byte == b'}') {
+            return i;
+        } else {
+            match byte {
+                b'"' | b'\'' => quote = Some(byte),
+                b'(' | b'[' => depth += 1,
+                b')' | b']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    end
+}
+
+/// Index of the `}` closing the block opened at `open`, or `end` if it is never closed
+fn closing_brace(text: &str, open: usize, end: usize) -> usize {
+    let mut depth = 0usize;
+    let mut i = open;
+    loop {
+        i = find_top_level(text, i, end, b"{}");
+        if i >= end {
+            return end;
+        }
+        if text.as_bytes()[i] == b'{' {
+            depth += 1;
+        } else {
+            depth -= 1;
+            if depth == 0 {
+                return i;
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Shrink `start..end` to exclude surrounding whitespace
+fn trim(text: &str, start: usize, end: usize) -> (usize, usize) {
+    let slice = &text[start..end];
+    let trimmed_start = start + (slice.len() - slice.trim_start().len());
+    let trimmed_end = start + slice.trim_end().len();
+    (trimmed_start, trimmed_end.max(trimmed_start))
+}
+
+struct StyleParser<'a> {
+    text: &'a str,
+    offset: usize,
+    rules: Vec<StyleRule>,
+}
+
+impl StyleParser<'_> {
+    fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(self.offset + start, self.offset + end)
+    }
+
+    /// Parse the items in `start..end` and return the declarations directly inside the block
+    fn block(
+        &mut self,
+        start: usize,
+        end: usize,
+        parents: &[Selector],
+        conditions: &[String],
+    ) -> Vec<Declaration> {
+        let mut declarations = vec![];
+        let mut pos = start;
+        while pos < end {
+            let stop = find_top_level(self.text, pos, end, b"{};");
+            if stop < end && self.text.as_bytes()[stop] == b'{' {
+                let close = closing_brace(self.text, stop, end);
+                self.nested(pos, stop, close, parents, conditions);
+                pos = close + 1;
+            } else {
+                // A `}` at this level is a stray closing brace and is skipped
+                declarations.extend(self.declaration(pos, stop));
+                pos = stop + 1;
+            }
+        }
+        declarations
+    }
+
+    /// Parse a block with the prelude at `start..open` and the content up to `close`
+    fn nested(
+        &mut self,
+        start: usize,
+        open: usize,
+        close: usize,
+        parents: &[Selector],
+        conditions: &[String],
+    ) {
+        let (start, end) = trim(self.text, start, open);
+        let prelude = &self.text[start..end];
+        let span = self.span(start, end);
+
+        if let Some(at_rule) = prelude.strip_prefix('@') {
+            let name = at_rule
+                .split(|c: char| !(c.is_alphanumeric() || c == '-'))
+                .next()
+                .unwrap_or_default()
+                .to_lowercase();
+            if !GROUPING_AT_RULES.contains(&name.as_str()) {
+                return;
+            }
+            let mut conditions = conditions.to_vec();
+            conditions.push(prelude.split_whitespace().collect::<Vec<_>>().join(" "));
+            if parents.is_empty() {
+                self.block(open + 1, close, parents, &conditions);
+            } else {
+                // `@media` inside a rule applies the rule's selectors under the condition
+                self.rule(parents.to_vec(), span, open, close, &conditions, true);
+            }
+        } else {
+            let selectors = self.selectors(start, end, parents);
+            self.rule(
+                selectors,
+                span,
+                open,
+                close,
+                conditions,
+                !parents.is_empty(),
+            );
+        }
+    }
+
+    fn rule(
+        &mut self,
+        selectors: Vec<Selector>,
+        span: Span,
+        open: usize,
+        close: usize,
+        conditions: &[String],
+        nested: bool,
+    ) {
+        // Reserve the rule's place first so it comes before its nested rules
+        let index = self.rules.len();
+        self.rules.push(StyleRule {
+            selectors: selectors.clone(),
+            declarations: vec![],
+            span,
+            conditions: conditions.to_vec(),
+            nested,
+        });
+        let declarations = self.block(open + 1, close, &selectors, conditions);
+        self.rules[index].declarations = declarations;
+    }
+
+    fn declaration(&self, start: usize, end: usize) -> Option<Declaration> {
+        let (start, end) = trim(self.text, start, end);
+        let text = &self.text[start..end];
+        // SCSS variables and at-rule statements such as `@include` aren't declarations
+        if text.starts_with(['@', '$']) {
+            return None;
+        }
+        let colon = text.find(':')?;
+        let property = text[..colon].trim_end();
+        if property.is_empty() || property.contains(char::is_whitespace) {
+            return None;
+        }
+
+        let mut value_end = end;
+        let mut important = false;
+        if let Some(bang) = text.rfind('!') {
+            if text[bang + 1..].trim().eq_ignore_ascii_case("important") {
+                important = true;
+                value_end = start + bang;
+            }
+        }
+        let (value_start, value_end) = trim(self.text, start + colon + 1, value_end);
+
+        Some(Declaration {
+            property: property.to_string(),
+            value: self.text[value_start..value_end].to_string(),
+            important,
+            span: self.span(start, end),
+            property_span: self.span(start, start + property.len()),
+            value_span: self.span(value_start, value_end),
+        })
+    }
+
+    /// Parse the selector list at `start..end`, resolving it against `parents`
+    fn selectors(&self, start: usize, end: usize, parents: &[Selector]) -> Vec<Selector> {
+        let mut selectors = vec![];
+        let mut pos = start;
+        while pos <= end {
+            let stop = find_top_level(self.text, pos, end, b",");
+            let (start, end) = trim(self.text, pos, stop);
+            if start < end {
+                if parents.is_empty() {
+                    selectors.push(self.selector(start, end, None));
+                } else {
+                    for parent in parents {
+                        selectors.push(self.selector(start, end, Some(parent)));
+                    }
+                }
+            }
+            pos = stop + 1;
+        }
+        selectors
+    }
+
+    fn selector(&self, start: usize, end: usize, parent: Option<&Selector>) -> Selector {
+        let text = self.text;
+        let mut compounds = vec![];
+        let mut current = Compound::default();
+        let mut uses_parent = false;
+        let mut i = start;
+
+        while i < end {
+            let c = text[i..].chars().next().unwrap_or_default();
+            match c {
+                c if c.is_whitespace() || matches!(c, '>' | '+' | '~') => {
+                    let mut combinator = Combinator::Descendant;
+                    while let Some(c) = text[i..end].chars().next() {
+                        match c {
+                            '>' => combinator = Combinator::Child,
+                            '+' => combinator = Combinator::NextSibling,
+                            '~' => combinator = Combinator::SubsequentSibling,
+                            c if c.is_whitespace() => {}
+                            _ => break,
+                        }
+                        i += c.len_utf8();
+                    }
+                    if !current.is_empty() {
+                        compounds.push(std::mem::take(&mut current));
+                    }
+                    current.combinator = combinator;
+                }
+                '.' | '#' | '%' => {
+                    let name_end = ident_end(text, i + 1, end);
+                    let name = SelectorName {
+                        name: unescape(&text[i + 1..name_end]),
+                        span: self.span(i, name_end),
+                    };
+                    match c {
+                        '.' => current.classes.push(name),
+                        '#' => current.ids.push(name),
+                        // SCSS placeholder selectors never match an element
+                        _ => current.pseudo_classes.push(PseudoClass {
+                            name: "placeholder".to_string(),
+                            argument: Some(name.name),
+                            selectors: vec![],
+                        }),
+                    }
+                    i = name_end.max(i + 1);
+                }
+                '[' => {
+                    let close = find_top_level(text, i + 1, end, b"]");
+                    current
+                        .attributes
+                        .push(attribute_selector(&text[i + 1..close]));
+                    i = close + 1;
+                }
+                ':' => {
+                    let double = text[i + 1..].starts_with(':');
+                    let name_start = i + 1 + usize::from(double);
+                    let name_end = ident_end(text, name_start, end);
+                    let name = text[name_start..name_end].to_lowercase();
+                    i = name_end.max(name_start);
+                    let mut argument = None;
+                    if text[i..end].starts_with('(') {
+                        let close = matching_paren(text, i, end);
+                        argument = Some((i + 1, close));
+                        i = (close + 1).min(end);
+                    }
+                    if double || LEGACY_PSEUDO_ELEMENTS.contains(&name.as_str()) {
+                        current.pseudo_element = Some(name);
+                    } else {
+                        let selectors = match argument {
+                            Some((start, end))
+                                if SELECTOR_PSEUDO_CLASSES.contains(&name.as_str()) =>
+                            {
+                                self.selectors(start, end, &[])
+                            }
+                            _ => vec![],
+                        };
+                        current.pseudo_classes.push(PseudoClass {
+                            name,
+                            argument: argument
+                                .map(|(start, end)| text[start..end].trim().to_string()),
+                            selectors,
+                        });
+                    }
+                }
+                '*' => {
+                    current.universal = true;
+                    i += 1;
+                }
+                '&' => {
+                    uses_parent = true;
+                    let suffix_end = ident_end(text, i + 1, end);
+                    if let Some(parent) = parent {
+                        if !current.is_empty() {
+                            compounds.push(std::mem::take(&mut current));
+                        }
+                        let combinator = current.combinator;
+                        let mut inherited = parent.compounds.clone();
+                        if let Some(first) = inherited.first_mut() {
+                            first.combinator = combinator;
+                        }
+                        current = inherited.pop().unwrap_or_default();
+                        compounds.extend(inherited);
+                        // `&__title` extends the parent's last name, as in SCSS
+                        if suffix_end > i + 1 {
+                            let suffix = unescape(&text[i + 1..suffix_end]);
+                            let span = self.span(i, suffix_end);
+                            if let Some(name) = current.classes.last_mut() {
+                                name.name.push_str(&suffix);
+                                name.span = span;
+                            } else if let Some(name) = current.ids.last_mut() {
+                                name.name.push_str(&suffix);
+                                name.span = span;
+                            } else if let Some(tag) = &mut current.tag {
+                                tag.push_str(&suffix);
+                            }
+                        }
+                    }
+                    i = suffix_end;
+                }
+                c if is_ident_char(c) || c == '\\' => {
+                    let name_end = ident_end(text, i, end);
+                    current.tag = Some(unescape(&text[i..name_end]));
+                    i = name_end.max(i + 1);
+                }
+                c => i += c.len_utf8(),
+            }
+        }
+        if !current.is_empty() {
+            compounds.push(current);
+        }
+
+        if let (Some(parent), false) = (parent, uses_parent) {
+            let mut resolved = parent.compounds.clone();
+            resolved.extend(compounds);
+            compounds = resolved;
+        }
+
+        Selector {
+            text: text[start..end].to_string(),
+            span: self.span(start, end),
+            compounds,
+        }
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+/// End of the identifier starting at `start`, including escaped characters
+fn ident_end(text: &str, start: usize, end: usize) -> usize {
+    let mut chars = text[start..end].char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if !is_ident_char(c) {
+            return start + i;
+        }
+    }
+    end
+}
+
+fn unescape(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.extend(chars.next()),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Index of the `)` closing the parenthesis at `open`, or `end`
+fn matching_paren(text: &str, open: usize, end: usize) -> usize {
+    let mut depth = 0usize;
+    for (i, byte) in text.as_bytes()[open..end].iter().enumerate() {
+        match byte {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + i;
+                }
+            }
+            _ => {}
+        }
+    }
+    end
+}
+
+/// Parse the content of an attribute selector without the brackets
+fn attribute_selector(text: &str) -> AttributeSelector {
+    let Some(equals) = text.find('=') else {
+        return AttributeSelector {
+            name: text.trim().to_string(),
+            operator: None,
+            value: None,
+        };
+    };
+    let before = &text[..equals];
+    let (name, operator) = match before.trim_end().char_indices().last() {
+        Some((i, c)) if matches!(c, '~' | '|' | '^' | '$' | '*') => (&before[..i], format!("{c}=")),
+        _ => (before, "=".to_string()),
+    };
+    let mut value = text[equals + 1..].trim();
+    // Drop a trailing case-sensitivity flag (`[type="a" i]`)
+    if let Some((quoted, flag)) = value.rsplit_once(char::is_whitespace) {
+        if matches!(flag, "i" | "I" | "s" | "S") {
+            value = quoted.trim_end();
+        }
+    }
+    let value = value
+        .strip_prefix(['"', '\''])
+        .and_then(|v| v.strip_suffix(['"', '\'']))
+        .unwrap_or(value);
+
+    AttributeSelector {
+        name: name.trim().to_string(),
+        operator: Some(operator),
+        value: Some(value.to_string()),
+    }
+}
@@ -0,0 +1,340 @@
+// Lightweight template parser
+// Builds an element tree with source spans from the <template> section. It is
+// deliberately lenient: unclosed elements are closed at the end of the template
+// and stray closing tags are ignored, so rules can still run on partially broken markup.
+
+use super::Span;
+use serde::{Deserialize, Serialize};
+
+/// HTML elements that never have children or a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Parsed template markup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Template {
+    /// Top-level nodes of the template
+    pub nodes: Vec<Node>,
+}
+
+/// A node in the template tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Node {
+    /// An element such as `<button>`
+    Element(Element),
+    /// Static text between tags
+    Text { text: String, span: Span },
+    /// A `{{ expression }}` interpolation
+    Interpolation { expression: String, span: Span },
+    /// An HTML comment
+    Comment { text: String, span: Span },
+}
+
+/// A template element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Element {
+    /// Tag name as written in the source
+    pub tag: String,
+    /// Attributes in source order
+    pub attributes: Vec<Attribute>,
+    /// Child nodes
+    pub children: Vec<Node>,
+    /// Span of the opening tag
+    pub span: Span,
+}
+
+/// An attribute on a template element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attribute {
+    /// Attribute name including any `@` or `:` prefix
+    pub name: String,
+    /// Attribute value without quotes, if one was given
+    pub value: Option<String>,
+    /// Span of the whole attribute
+    pub span: Span,
+    /// Span of the value without quotes
+    pub value_span: Option<Span>,
+}
+
+impl Node {
+    /// Span of the node; for elements, the opening tag
+    pub fn span(&self) -> Span {
+        match self {
+            Node::Element(element) => element.span,
+            Node::Text { span, .. }
+            | Node::Interpolation { span, .. }
+            | Node::Comment { span, .. } => *span,
+        }
+    }
+}
+
+impl Attribute {
+    /// Event name for `@event` directives
+    pub fn event_name(&self) -> Option<&str> {
+        self.name.strip_prefix('@')
+    }
+
+    /// Bound attribute name for `:attr` bindings
+    pub fn bound_name(&self) -> Option<&str> {
+        self.name.strip_prefix(':')
+    }
+}
+
+impl Element {
+    /// Find an attribute by its exact name
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    /// Whether the element has an attribute with the given exact name
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attribute(name).is_some()
+    }
+
+    /// Classes listed in a static `class` attribute
+    pub fn classes(&self) -> impl Iterator<Item = &str> {
+        self.attribute("class")
+            .and_then(|a| a.value.as_deref())
+            .unwrap_or_default()
+            .split_whitespace()
+    }
+
+    /// Span from the opening tag to the end of the last descendant (the closing tag is not tracked)
+    pub fn extent(&self) -> Span {
+        let end = match self.children.last() {
+            Some(Node::Element(child)) => child.extent().end,
+            Some(node) => node.span().end,
+            None => self.span.end,
+        };
+        Span::new(self.span.start, end)
+    }
+
+    /// Child elements, skipping text, interpolation and comment nodes
+    pub fn child_elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            _ => None,
+        })
+    }
+}
+
+impl Template {
+    /// Parse the template markup covered by `span` in `text`
+    pub fn parse(text: &str, span: Span) -> Self {
+        let mut parser = TemplateParser {
+            text,
+            pos: span.start,
+            end: span.end,
+            stack: vec![],
+            nodes: vec![],
+        };
+        parser.run();
+        Self {
+            nodes: parser.nodes,
+        }
+    }
+
+    /// Visit every node in document order, along with its ancestor elements
+    pub fn visit<'a>(&'a self, f: &mut impl FnMut(&'a Node, &[&'a Element])) {
+        let mut ancestors = vec![];
+        for node in &self.nodes {
+            visit_node(node, &mut ancestors, f);
+        }
+    }
+
+    /// All elements in document order
+    pub fn elements(&self) -> Vec<&Element> {
+        let mut elements = vec![];
+        self.visit(&mut |node, _| {
+            if let Node::Element(element) = node {
+                elements.push(element);
+            }
+        });
+        elements
+    }
+}
+
+fn visit_node<'a>(
+    node: &'a Node,
+    ancestors: &mut Vec<&'a Element>,
+    f: &mut impl FnMut(&'a Node, &[&'a Element]),
+) {
+    f(node, ancestors);
+    if let Node::Element(element) = node {
+        ancestors.push(element);
+        for child in &element.children {
+            visit_node(child, ancestors, f);
+        }
+        ancestors.pop();
+    }
+}
+
+struct TemplateParser<'a> {
+    text: &'a str,
+    pos: usize,
+    end: usize,
+    stack: Vec<Element>,
+    nodes: Vec<Node>,
+}
+
+impl<'a> TemplateParser<'a> {
+    fn run(&mut self) {
+        while self.pos < self.end {
+            let rest = &self.text[self.pos..self.end];
+            if rest.starts_with("<!--") {
+                let body_end = rest.find("-->").unwrap_or(rest.len());
+                let span = Span::new(self.pos, (self.pos + body_end + 3).min(self.end));
+                let text = rest[4..body_end.max(4)].trim().to_string();
+                self.attach(Node::Comment { text, span });
+                self.pos = span.end;
+            } else if let Some(after) = rest.strip_prefix("</") {
+                let close = after.find('>').map(|i| i + 1).unwrap_or(after.len());
+                let tag = after[..close].trim_end_matches('>').trim();
+                self.close_element(tag);
+                self.pos += 2 + close;
+            } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_alphabetic()) {
+                self.open_element();
+            } else if let Some(after) = rest.strip_prefix("{{") {
+                let close = after.find("}}").unwrap_or(after.len());
+                let expression = after[..close].trim().to_string();
+                let span = Span::new(self.pos, (self.pos + 2 + close + 2).min(self.end));
+                self.attach(Node::Interpolation { expression, span });
+                self.pos = span.end;
+            } else {
+                let first = rest.chars().next().map_or(1, char::len_utf8);
+                let len = rest[first..].find('<').map_or(rest.len(), |i| i + first);
+                let len = rest[first..len].find("{{").map_or(len, |i| i + first);
+                let text = &rest[..len];
+                if !text.trim().is_empty() {
+                    self.attach(Node::Text {
+                        text: text.to_string(),
+                        span: Span::new(self.pos, self.pos + len),
+                    });
+                }
+                self.pos += len;
+            }
+        }
+
+        while let Some(element) = self.stack.pop() {
+            self.attach(Node::Element(element));
+        }
+    }
+
+    fn attach(&mut self, node: Node) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => self.nodes.push(node),
+        }
+    }
+
+    fn close_element(&mut self, tag: &str) {
+        // Ignore closing tags that don't match any open element
+        if !self.stack.iter().any(|e| e.tag == tag) {
+            return;
+        }
+        while let Some(element) = self.stack.pop() {
+            let done = element.tag == tag;
+            self.attach(Node::Element(element));
+            if done {
+                break;
+            }
+        }
+    }
+
+    fn open_element(&mut self) {
+        let start = self.pos;
+        self.pos += 1;
+        let tag = self.take_while(|c| !c.is_whitespace() && c != '>' && c != '/');
+        let mut attributes = vec![];
+        let mut self_closing = false;
+
+        loop {
+            self.take_while(char::is_whitespace);
+            let rest = &self.text[self.pos..self.end];
+            if rest.is_empty() {
+                break;
+            }
+            if rest.starts_with("/>") {
+                self_closing = true;
+                self.pos += 2;
+                break;
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            if rest.starts_with('/') {
+                self.pos += 1;
+                continue;
+            }
+            attributes.push(self.attribute());
+        }
+
+        let element = Element {
+            tag: tag.to_string(),
+            attributes,
+            children: vec![],
+            span: Span::new(start, self.pos),
+        };
+
+        if self_closing || VOID_ELEMENTS.contains(&element.tag.to_lowercase().as_str()) {
+            self.attach(Node::Element(element));
+        } else {
+            self.stack.push(element);
+        }
+    }
+
+    fn attribute(&mut self) -> Attribute {
+        let start = self.pos;
+        let name = self
+            .take_while(|c| !c.is_whitespace() && c != '=' && c != '>' && c != '/')
+            .to_string();
+        let name_end = self.pos;
+        self.take_while(char::is_whitespace);
+
+        if !self.text[self.pos..self.end].starts_with('=') {
+            // Boolean attribute; don't swallow the whitespace before the next one
+            self.pos = name_end;
+            return Attribute {
+                name,
+                value: None,
+                span: Span::new(start, name_end),
+                value_span: None,
+            };
+        }
+
+        self.pos += 1;
+        self.take_while(char::is_whitespace);
+        let rest = &self.text[self.pos..self.end];
+        let value_span = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let len = rest[1..].find(quote).unwrap_or(rest.len() - 1);
+                let span = Span::new(self.pos + 1, self.pos + 1 + len);
+                self.pos = (span.end + 1).min(self.end);
+                span
+            }
+            _ => {
+                let value_start = self.pos;
+                self.take_while(|c| !c.is_whitespace() && c != '>');
+                Span::new(value_start, self.pos)
+            }
+        };
+
+        Attribute {
+            name,
+            value: Some(self.text[value_span.start..value_span.end].to_string()),
+            span: Span::new(start, self.pos),
+            value_span: Some(value_span),
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.text[self.pos..self.end];
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+}
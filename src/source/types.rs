@@ -0,0 +1,977 @@
+// Type annotations and literal values
+// Parses Orbit's type syntax, which mixes TypeScript (`number | null`, `"2d" | "3d"`,
+// `(value: string) => void`, `string[]`) and Rust (`Option<String>`, `Vec<i32>`,
+// `Fn(i32) -> bool`), and checks literal initial values against the parsed types.
+
+use super::script::{tokenize, Token, TokenKind};
+use super::Span;
+
+/// A parsed type annotation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type {
+    /// What kind of type this is
+    pub kind: TypeKind,
+    /// Span of the type in the file
+    pub span: Span,
+}
+
+/// Kind of a type
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeKind {
+    /// A named type with optional generic arguments, e.g. `number`, `Option<T>` or `std::rc::Rc<T>`
+    Named {
+        /// Name, with path segments joined by `::`
+        name: String,
+        /// Span of the name
+        name_span: Span,
+        /// Generic arguments
+        args: Vec<Type>,
+    },
+    /// A literal type such as `"2d"`, `42` or `true`
+    Literal(Literal),
+    /// `T[]` or Rust's `[T; N]`
+    Array(Box<Type>),
+    /// `[A, B]` or Rust's `(A, B)`; `()` is the empty tuple
+    Tuple(Vec<Type>),
+    /// An object type such as `{ x: number, y: number }`
+    Object(Vec<(String, Type)>),
+    /// `A | B`
+    Union(Vec<Type>),
+    /// `(a: A) => R` or `Fn(A) -> R`; a missing Rust return type is `()`
+    Function {
+        /// Parameter types
+        params: Vec<Type>,
+        /// Return type
+        ret: Box<Type>,
+    },
+}
+
+/// A literal type or value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    /// String contents, without quotes
+    String(String),
+    /// Number as written
+    Number(String),
+    /// `true` or `false`
+    Boolean(bool),
+}
+
+/// A type annotation that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeError {
+    /// What is wrong
+    pub message: String,
+    /// Where
+    pub span: Span,
+}
+
+/// Built-in primitive types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primitive {
+    String,
+    Number {
+        integer: bool,
+        unsigned: bool,
+    },
+    Boolean,
+    Null,
+    Undefined,
+    /// `any` and `unknown`, which accept every value
+    Any,
+}
+
+impl Primitive {
+    /// The primitive named `name`, covering both TypeScript and Rust spellings
+    pub fn from_name(name: &str) -> Option<Self> {
+        let number = |integer, unsigned| Primitive::Number { integer, unsigned };
+        Some(match name {
+            "string" | "String" | "str" | "char" => Primitive::String,
+            "number" | "f32" | "f64" => number(false, false),
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => number(true, false),
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => number(true, true),
+            "boolean" | "bool" => Primitive::Boolean,
+            "null" => Primitive::Null,
+            "undefined" | "void" => Primitive::Undefined,
+            "any" | "unknown" => Primitive::Any,
+            _ => return None,
+        })
+    }
+}
+
+/// Built-in generic types and the number of type arguments they take
+const GENERIC_TYPES: &[(&str, usize)] = &[
+    ("Option", 1),
+    ("Vec", 1),
+    ("VecDeque", 1),
+    ("HashMap", 2),
+    ("HashSet", 1),
+    ("BTreeMap", 2),
+    ("BTreeSet", 1),
+    ("Box", 1),
+    ("Rc", 1),
+    ("Arc", 1),
+    ("Cell", 1),
+    ("RefCell", 1),
+    ("Result", 2),
+    ("Array", 1),
+    ("Record", 2),
+    ("Map", 2),
+    ("Set", 1),
+    ("Promise", 1),
+];
+
+/// Built-in types other than primitives that take no type arguments
+const OTHER_TYPES: &[&str] = &[
+    "Date", "Function", "object", "Object", "never", "bigint", "symbol", "Self",
+];
+
+/// Number of type arguments a built-in type takes, or `None` if `name` isn't built in
+pub fn builtin_arity(name: &str) -> Option<usize> {
+    if Primitive::from_name(name).is_some() || OTHER_TYPES.contains(&name) {
+        return Some(0);
+    }
+    GENERIC_TYPES
+        .iter()
+        .find(|(generic, _)| *generic == name)
+        .map(|(_, arity)| *arity)
+}
+
+/// Names of all built-in types
+pub fn builtin_types() -> impl Iterator<Item = &'static str> {
+    const PRIMITIVES: &[&str] = &[
+        "string",
+        "String",
+        "str",
+        "char",
+        "number",
+        "f32",
+        "f64",
+        "i8",
+        "i16",
+        "i32",
+        "i64",
+        "i128",
+        "isize",
+        "u8",
+        "u16",
+        "u32",
+        "u64",
+        "u128",
+        "usize",
+        "boolean",
+        "bool",
+        "null",
+        "undefined",
+        "void",
+        "any",
+        "unknown",
+    ];
+    PRIMITIVES
+        .iter()
+        .chain(OTHER_TYPES)
+        .copied()
+        .chain(GENERIC_TYPES.iter().map(|(name, _)| *name))
+}
+
+/// Types declared or imported in a script section: `type`, `struct`, `enum`, `interface`,
+/// `class` and `trait` declarations, `use` paths and `import` statements
+pub fn declared_types(tokens: &[Token]) -> Vec<String> {
+    const DECLARATIONS: &[&str] = &["type", "struct", "enum", "interface", "class", "trait"];
+    let mut types = vec![];
+
+    for (i, token) in tokens.iter().enumerate() {
+        let next = tokens.get(i + 1).filter(|t| t.kind == TokenKind::Ident);
+        if DECLARATIONS.contains(&token.text.as_str()) && token.kind == TokenKind::Ident {
+            types.extend(next.map(|t| t.text.clone()));
+        } else if token.is_ident("use") || token.is_ident("import") {
+            let end = tokens[i..]
+                .iter()
+                .position(|t| t.is_punct(";") || t.is_ident("from"))
+                .map_or(tokens.len(), |end| i + end);
+            // Every imported name counts; `as` renames replace the original name
+            for (j, name) in tokens.iter().enumerate().take(end).skip(i + 1) {
+                let renamed = tokens.get(j + 1).is_some_and(|t| t.is_ident("as"));
+                let is_path = tokens.get(j + 1).is_some_and(|t| t.is_punct("::"));
+                if name.kind == TokenKind::Ident && !renamed && !is_path && name.text != "as" {
+                    types.push(name.text.clone());
+                }
+            }
+        }
+    }
+
+    types
+}
+
+/// A literal value, as far as it can be recognised from source text
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A string, with its contents if they are fixed
+    String(Option<String>),
+    Number {
+        integer: bool,
+        negative: bool,
+        text: String,
+    },
+    Boolean(bool),
+    /// `null` or Rust's `None`
+    Null,
+    Undefined,
+    /// An array literal or `vec![...]`
+    Array(Vec<Value>),
+    Object,
+    /// An arrow function, `function` expression or Rust closure
+    Function,
+    /// Anything that isn't a literal, such as a call or a reference to another name
+    Unknown,
+}
+
+/// Parse a type annotation; `offset` is the position of `text` in the file
+pub fn parse_type(text: &str, offset: usize) -> Result<Type, TypeError> {
+    let mut parser = TypeParser {
+        tokens: split_angles(tokenize(text, offset)),
+        pos: 0,
+        end: offset + text.len(),
+    };
+    let ty = parser.union()?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(ty),
+        Some(token) => Err(TypeError {
+            message: format!("unexpected '{}' in type", token.text),
+            span: token.span,
+        }),
+    }
+}
+
+/// Recognise a literal value; anything that isn't a literal is [`Value::Unknown`]
+pub fn parse_value(text: &str, offset: usize) -> Value {
+    let tokens = tokenize(text, offset);
+    match value(&tokens) {
+        Some((value, used)) if used == tokens.len() => value,
+        _ => Value::Unknown,
+    }
+}
+
+impl Type {
+    /// Call `f` on this type and every type nested in it
+    pub fn visit(&self, f: &mut impl FnMut(&Type)) {
+        f(self);
+        let children: Vec<&Type> = match &self.kind {
+            TypeKind::Named { args, .. } => args.iter().collect(),
+            TypeKind::Literal(_) => vec![],
+            TypeKind::Array(element) => vec![element],
+            TypeKind::Tuple(elements) | TypeKind::Union(elements) => elements.iter().collect(),
+            TypeKind::Object(fields) => fields.iter().map(|(_, ty)| ty).collect(),
+            TypeKind::Function { params, ret } => params.iter().chain([&**ret]).collect(),
+        };
+        for child in children {
+            child.visit(f);
+        }
+    }
+
+    /// Whether values of this type can be called, looking through unions and `Option`/`Box`
+    pub fn is_function(&self) -> bool {
+        match &self.kind {
+            TypeKind::Function { .. } => true,
+            TypeKind::Union(members) => members.iter().any(Type::is_function),
+            TypeKind::Named { name, args, .. } => match name.as_str() {
+                "Function" => true,
+                "Option" | "Box" | "Rc" | "Arc" => args.first().is_some_and(Type::is_function),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether the type admits a missing value: `Option<T>`, `null`, `undefined` or a union with one
+    pub fn is_nullable(&self) -> bool {
+        match &self.kind {
+            TypeKind::Union(members) => members.iter().any(Type::is_nullable),
+            TypeKind::Named { name, .. } => {
+                matches!(name.as_str(), "Option" | "null" | "undefined")
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the type accepts a value: `None` when that can't be decided, for example for
+    /// user-defined types or values that aren't literals
+    pub fn accepts(&self, value: &Value) -> Option<bool> {
+        if *value == Value::Unknown {
+            return None;
+        }
+        match &self.kind {
+            TypeKind::Named { name, args, .. } => named_accepts(name, args, value),
+            TypeKind::Literal(literal) => Some(match (literal, value) {
+                (Literal::String(expected), Value::String(actual)) => {
+                    actual.as_ref().map(|actual| expected == actual)?
+                }
+                (Literal::Number(expected), Value::Number { text, negative, .. }) => {
+                    let sign = if *negative { "-" } else { "" };
+                    expected.parse::<f64>().ok() == format!("{sign}{text}").parse::<f64>().ok()
+                }
+                (Literal::Boolean(expected), Value::Boolean(actual)) => expected == actual,
+                _ => false,
+            }),
+            TypeKind::Array(element) => match value {
+                Value::Array(items) => all_accepted(items.iter().map(|v| element.accepts(v))),
+                _ => Some(false),
+            },
+            TypeKind::Tuple(elements) => match value {
+                Value::Array(items) if items.len() == elements.len() => {
+                    all_accepted(elements.iter().zip(items).map(|(ty, v)| ty.accepts(v)))
+                }
+                _ => Some(false),
+            },
+            TypeKind::Object(_) => Some(*value == Value::Object),
+            TypeKind::Function { .. } => Some(*value == Value::Function),
+            TypeKind::Union(members) => {
+                let mut undecided = false;
+                for member in members {
+                    match member.accepts(value) {
+                        Some(true) => return Some(true),
+                        Some(false) => {}
+                        None => undecided = true,
+                    }
+                }
+                if undecided {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+        }
+    }
+}
+
+/// Whether a named type accepts a value
+fn named_accepts(name: &str, args: &[Type], value: &Value) -> Option<bool> {
+    if let Some(primitive) = Primitive::from_name(name) {
+        return Some(match (primitive, value) {
+            (Primitive::Any, _) => true,
+            (Primitive::String, Value::String(_)) => true,
+            (
+                Primitive::Number { integer, unsigned },
+                Value::Number {
+                    integer: is_integer,
+                    negative,
+                    ..
+                },
+            ) => (!integer || *is_integer) && !(unsigned && *negative),
+            (Primitive::Boolean, Value::Boolean(_)) => true,
+            (Primitive::Null, Value::Null) => true,
+            (Primitive::Undefined, Value::Undefined) => true,
+            _ => false,
+        });
+    }
+
+    let inner = args.first();
+    match name.rsplit("::").next().unwrap_or(name) {
+        "Option" => match value {
+            Value::Null | Value::Undefined => Some(true),
+            _ => inner?.accepts(value),
+        },
+        "Vec" | "Array" | "VecDeque" | "HashSet" | "BTreeSet" | "Set" => match value {
+            Value::Array(items) => match inner {
+                Some(inner) => all_accepted(items.iter().map(|v| inner.accepts(v))),
+                None => Some(true),
+            },
+            _ => Some(false),
+        },
+        "Box" | "Rc" | "Arc" | "Cell" | "RefCell" => inner?.accepts(value),
+        "Function" => Some(*value == Value::Function),
+        "Record" | "Map" | "HashMap" | "BTreeMap" | "object" | "Object" => {
+            Some(*value == Value::Object)
+        }
+        _ => None,
+    }
+}
+
+/// Combine the results for the parts of a value: `false` if any part is rejected
+fn all_accepted(results: impl Iterator<Item = Option<bool>>) -> Option<bool> {
+    let mut undecided = false;
+    for result in results {
+        match result {
+            Some(false) => return Some(false),
+            None => undecided = true,
+            Some(true) => {}
+        }
+    }
+    if undecided {
+        None
+    } else {
+        Some(true)
+    }
+}
+
+/// Split `>>` and `>>>` into single `>` tokens so nested generics close one at a time
+fn split_angles(tokens: Vec<Token>) -> Vec<Token> {
+    let mut split = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        if token.kind == TokenKind::Punct && token.text.chars().all(|c| c == '>') {
+            for i in 0..token.text.len() {
+                split.push(Token {
+                    kind: TokenKind::Punct,
+                    text: ">".to_string(),
+                    span: Span::new(token.span.start + i, token.span.start + i + 1),
+                });
+            }
+        } else {
+            split.push(token);
+        }
+    }
+    split
+}
+
+struct TypeParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// End of the text, for errors at the end of input
+    end: usize,
+}
+
+impl TypeParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_punct(&self, punct: &str) -> bool {
+        self.peek().is_some_and(|t| t.is_punct(punct))
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        let matched = self.peek_punct(punct);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<Span, TypeError> {
+        match self.peek() {
+            Some(token) if token.is_punct(punct) => {
+                let span = token.span;
+                self.pos += 1;
+                Ok(span)
+            }
+            Some(token) => Err(TypeError {
+                message: format!("expected '{punct}' but found '{}'", token.text),
+                span: token.span,
+            }),
+            None => Err(self.end_error(&format!("expected '{punct}'"))),
+        }
+    }
+
+    fn end_error(&self, message: &str) -> TypeError {
+        TypeError {
+            message: format!("{message} at the end of the type"),
+            span: Span::new(self.end, self.end),
+        }
+    }
+
+    /// Start of the previous token's span to the end of the last consumed token
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(start, self.tokens[self.pos - 1].span.end)
+    }
+
+    /// `A | B | C`, with an optional leading `|`
+    fn union(&mut self) -> Result<Type, TypeError> {
+        let start = self.peek().map_or(self.end, |t| t.span.start);
+        self.eat_punct("|");
+        let mut members = vec![self.postfix()?];
+        while self.eat_punct("|") {
+            members.push(self.postfix()?);
+        }
+        if members.len() == 1 {
+            return Ok(members.remove(0));
+        }
+        Ok(Type {
+            kind: TypeKind::Union(members),
+            span: self.span_from(start),
+        })
+    }
+
+    /// A primary type followed by any number of `[]`
+    fn postfix(&mut self) -> Result<Type, TypeError> {
+        let mut ty = self.primary()?;
+        while self.peek_punct("[")
+            && self
+                .tokens
+                .get(self.pos + 1)
+                .is_some_and(|t| t.is_punct("]"))
+        {
+            self.pos += 2;
+            let span = Span::new(ty.span.start, self.tokens[self.pos - 1].span.end);
+            ty = Type {
+                kind: TypeKind::Array(Box::new(ty)),
+                span,
+            };
+        }
+        Ok(ty)
+    }
+
+    fn primary(&mut self) -> Result<Type, TypeError> {
+        let Some(token) = self.peek().cloned() else {
+            return Err(self.end_error("expected a type"));
+        };
+        let start = token.span.start;
+
+        match token.kind {
+            TokenKind::Str => {
+                self.pos += 1;
+                let text = &token.text;
+                Ok(Type {
+                    kind: TypeKind::Literal(Literal::String(text[1..text.len() - 1].to_string())),
+                    span: token.span,
+                })
+            }
+            TokenKind::Number => {
+                self.pos += 1;
+                Ok(Type {
+                    kind: TypeKind::Literal(Literal::Number(token.text.clone())),
+                    span: token.span,
+                })
+            }
+            TokenKind::Ident => self.named(),
+            TokenKind::Punct => match token.text.as_str() {
+                "-" if self
+                    .tokens
+                    .get(self.pos + 1)
+                    .is_some_and(|t| t.kind == TokenKind::Number) =>
+                {
+                    self.pos += 2;
+                    Ok(Type {
+                        kind: TypeKind::Literal(Literal::Number(format!(
+                            "-{}",
+                            self.tokens[self.pos - 1].text
+                        ))),
+                        span: self.span_from(start),
+                    })
+                }
+                // References and raw pointers are transparent
+                "&" | "*" => {
+                    self.pos += 1;
+                    while self
+                        .peek()
+                        .is_some_and(|t| t.is_ident("mut") || t.is_ident("const"))
+                    {
+                        self.pos += 1;
+                    }
+                    self.postfix()
+                }
+                "(" => self.parenthesised(),
+                "[" => {
+                    self.pos += 1;
+                    let first = self.union()?;
+                    // Rust arrays: `[T; N]` or slices `[T]`
+                    if self.eat_punct(";") {
+                        self.pos += 1;
+                        self.expect_punct("]")?;
+                        return Ok(Type {
+                            kind: TypeKind::Array(Box::new(first)),
+                            span: self.span_from(start),
+                        });
+                    }
+                    if self.eat_punct("]") {
+                        return Ok(Type {
+                            kind: TypeKind::Tuple(vec![first]),
+                            span: self.span_from(start),
+                        });
+                    }
+                    let mut elements = vec![first];
+                    while self.eat_punct(",") {
+                        if self.peek_punct("]") {
+                            break;
+                        }
+                        elements.push(self.union()?);
+                    }
+                    self.expect_punct("]")?;
+                    Ok(Type {
+                        kind: TypeKind::Tuple(elements),
+                        span: self.span_from(start),
+                    })
+                }
+                "{" => {
+                    self.pos += 1;
+                    let mut fields = vec![];
+                    while !self.eat_punct("}") {
+                        let name = match self.peek() {
+                            Some(t) if matches!(t.kind, TokenKind::Ident | TokenKind::Str) => {
+                                t.text.trim_matches(['"', '\'']).to_string()
+                            }
+                            Some(t) => {
+                                return Err(TypeError {
+                                    message: format!(
+                                        "expected a field name but found '{}'",
+                                        t.text
+                                    ),
+                                    span: t.span,
+                                })
+                            }
+                            None => return Err(self.end_error("expected '}'")),
+                        };
+                        self.pos += 1;
+                        self.eat_punct("?");
+                        self.expect_punct(":")?;
+                        fields.push((name, self.union()?));
+                        if !self.eat_punct(",") && !self.eat_punct(";") {
+                            self.expect_punct("}")?;
+                            break;
+                        }
+                    }
+                    Ok(Type {
+                        kind: TypeKind::Object(fields),
+                        span: self.span_from(start),
+                    })
+                }
+                _ => Err(TypeError {
+                    message: format!("expected a type but found '{}'", token.text),
+                    span: token.span,
+                }),
+            },
+        }
+    }
+
+    /// `(T)`, a tuple `(A, B)` or an arrow function type `(a: A, b?: B) => R`
+    fn parenthesised(&mut self) -> Result<Type, TypeError> {
+        let start = self.tokens[self.pos].span.start;
+        let close = self.matching_paren(self.pos);
+        let is_function =
+            close.is_some_and(|close| self.tokens.get(close + 1).is_some_and(|t| t.is_punct("=>")));
+
+        self.pos += 1;
+        let mut elements = vec![];
+        let mut trailing_comma = false;
+        while !self.eat_punct(")") {
+            if is_function {
+                elements.push(self.param()?);
+            } else {
+                elements.push(self.union()?);
+            }
+            trailing_comma = self.eat_punct(",");
+            if !trailing_comma {
+                self.expect_punct(")")?;
+                break;
+            }
+        }
+
+        if is_function {
+            self.expect_punct("=>")?;
+            let ret = self.union()?;
+            return Ok(Type {
+                kind: TypeKind::Function {
+                    params: elements,
+                    ret: Box::new(ret),
+                },
+                span: self.span_from(start),
+            });
+        }
+        if elements.len() == 1 && !trailing_comma {
+            let mut inner = elements.remove(0);
+            inner.span = self.span_from(start);
+            return Ok(inner);
+        }
+        Ok(Type {
+            kind: TypeKind::Tuple(elements),
+            span: self.span_from(start),
+        })
+    }
+
+    /// An arrow function parameter: `name: T`, `name?: T`, `...names: T[]` or a bare type
+    fn param(&mut self) -> Result<Type, TypeError> {
+        self.eat_punct("...");
+        let named = self.peek().is_some_and(|t| t.kind == TokenKind::Ident)
+            && self.tokens.get(self.pos + 1).is_some_and(|t| {
+                t.is_punct(":")
+                    || (t.is_punct("?")
+                        && self
+                            .tokens
+                            .get(self.pos + 2)
+                            .is_some_and(|t| t.is_punct(":")))
+            });
+        if named {
+            self.pos += 1;
+            self.eat_punct("?");
+            self.expect_punct(":")?;
+        }
+        self.union()
+    }
+
+    /// A named type, `Fn(A) -> R`, or a `true`/`false` literal type
+    fn named(&mut self) -> Result<Type, TypeError> {
+        let token = self.tokens[self.pos].clone();
+        let start = token.span.start;
+
+        if token.text == "true" || token.text == "false" {
+            self.pos += 1;
+            return Ok(Type {
+                kind: TypeKind::Literal(Literal::Boolean(token.text == "true")),
+                span: token.span,
+            });
+        }
+        if token.text == "dyn" || token.text == "impl" {
+            self.pos += 1;
+            return self.primary();
+        }
+        if ["Fn", "FnMut", "FnOnce", "fn"].contains(&token.text.as_str())
+            && self
+                .tokens
+                .get(self.pos + 1)
+                .is_some_and(|t| t.is_punct("("))
+        {
+            self.pos += 2;
+            let mut params = vec![];
+            while !self.eat_punct(")") {
+                params.push(self.union()?);
+                if !self.eat_punct(",") {
+                    self.expect_punct(")")?;
+                    break;
+                }
+            }
+            let ret = if self.eat_punct("->") {
+                self.union()?
+            } else {
+                Type {
+                    kind: TypeKind::Tuple(vec![]),
+                    span: self.span_from(start),
+                }
+            };
+            return Ok(Type {
+                kind: TypeKind::Function {
+                    params,
+                    ret: Box::new(ret),
+                },
+                span: self.span_from(start),
+            });
+        }
+
+        let mut name = token.text.clone();
+        self.pos += 1;
+        while self.peek_punct("::") || self.peek_punct(".") {
+            let separator = self.tokens[self.pos].text.clone();
+            match self.tokens.get(self.pos + 1) {
+                Some(t) if t.kind == TokenKind::Ident => {
+                    name.push_str(&separator);
+                    name.push_str(&t.text);
+                    self.pos += 2;
+                }
+                Some(t) => {
+                    return Err(TypeError {
+                        message: format!(
+                            "expected a name after '{separator}' but found '{}'",
+                            t.text
+                        ),
+                        span: t.span,
+                    })
+                }
+                None => return Err(self.end_error("expected a name")),
+            }
+        }
+        let name_span = self.span_from(start);
+
+        let mut args = vec![];
+        if self.eat_punct("<") {
+            loop {
+                args.push(self.union()?);
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct(">")?;
+        }
+
+        Ok(Type {
+            kind: TypeKind::Named {
+                name,
+                name_span,
+                args,
+            },
+            span: self.span_from(start),
+        })
+    }
+
+    fn matching_paren(&self, open: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        for (i, token) in self.tokens.iter().enumerate().skip(open) {
+            if token.is_punct("(") {
+                depth += 1;
+            } else if token.is_punct(")") {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Recognise a literal at the start of `tokens`, returning it and the number of tokens used
+fn value(tokens: &[Token]) -> Option<(Value, usize)> {
+    let first = tokens.first()?;
+    let next = tokens.get(1);
+
+    let simple = match first.kind {
+        TokenKind::Str => {
+            let text = &first.text;
+            let inner = &text[1..text.len().max(2) - 1];
+            // Template literals with substitutions are strings, but not a fixed one
+            if text.starts_with('`') && inner.contains("${") {
+                Some(Value::String(None))
+            } else {
+                Some(Value::String(Some(inner.to_string())))
+            }
+        }
+        TokenKind::Number => Some(number(&first.text, false)),
+        _ => None,
+    };
+    if let Some(simple) = simple {
+        // `"text".to_string()`, `"text".into()` and `"text".to_owned()`
+        if matches!(simple, Value::String(_))
+            && next.is_some_and(|t| t.is_punct("."))
+            && tokens.get(2).is_some_and(|t| {
+                t.is_ident("to_string") || t.is_ident("into") || t.is_ident("to_owned")
+            })
+            && tokens.get(3).is_some_and(|t| t.is_punct("("))
+            && tokens.get(4).is_some_and(|t| t.is_punct(")"))
+        {
+            return Some((simple, 5));
+        }
+        return Some((simple, 1));
+    }
+
+    if first.is_punct("-") {
+        let number_token = next.filter(|t| t.kind == TokenKind::Number)?;
+        return Some((number(&number_token.text, true), 2));
+    }
+
+    match first.text.as_str() {
+        "true" | "false" if first.kind == TokenKind::Ident => {
+            return Some((Value::Boolean(first.text == "true"), 1))
+        }
+        "null" | "None" if first.kind == TokenKind::Ident => return Some((Value::Null, 1)),
+        "undefined" if first.kind == TokenKind::Ident => return Some((Value::Undefined, 1)),
+        _ => {}
+    }
+
+    // Functions: `() => ...`, `x => ...`, `async (x) => ...`, `function () {}`, `|x| ...`
+    let body_start = usize::from(first.is_ident("async"));
+    let head = tokens.get(body_start)?;
+    let arrow = if head.is_punct("(") {
+        close_of(tokens, body_start).map(|close| close + 1)
+    } else if head.kind == TokenKind::Ident {
+        Some(body_start + 1)
+    } else {
+        None
+    };
+    if arrow.is_some_and(|arrow| tokens.get(arrow).is_some_and(|t| t.is_punct("=>")))
+        || head.is_ident("function")
+        || head.is_punct("|")
+        || head.is_punct("||")
+        || head.is_ident("move")
+    {
+        return Some((Value::Function, tokens.len()));
+    }
+
+    // `Some(value)` is the value itself
+    if first.is_ident("Some") && next.is_some_and(|t| t.is_punct("(")) {
+        let close = close_of(tokens, 1)?;
+        let (inner, used) = value(&tokens[2..close])?;
+        return (used == close - 2).then_some((inner, close + 1));
+    }
+
+    // `String::new()`, `String::from("...")`, `Vec::new()`
+    if tokens.len() >= 4
+        && first.kind == TokenKind::Ident
+        && tokens[1].is_punct("::")
+        && tokens[3].is_punct("(")
+    {
+        let close = close_of(tokens, 3)?;
+        let value = match (first.text.as_str(), tokens[2].text.as_str()) {
+            ("String", "new") if close == 4 => Value::String(Some(String::new())),
+            ("String", "from") => match value(&tokens[4..close]) {
+                Some((string @ Value::String(_), used)) if used == close - 4 => string,
+                _ => return None,
+            },
+            ("Vec", "new") if close == 4 => Value::Array(vec![]),
+            _ => return None,
+        };
+        return Some((value, close + 1));
+    }
+
+    // Arrays: `[1, 2]` or `vec![1, 2]`
+    let open = if first.is_ident("vec") && next.is_some_and(|t| t.is_punct("!")) {
+        2
+    } else {
+        0
+    };
+    if tokens.get(open).is_some_and(|t| t.is_punct("[")) {
+        let close = close_of(tokens, open)?;
+        let mut items = vec![];
+        let mut i = open + 1;
+        while i < close {
+            let end = top_level_comma(&tokens[i..close]).map_or(close, |comma| i + comma);
+            if end > i {
+                items.push(match value(&tokens[i..end]) {
+                    Some((item, used)) if used == end - i => item,
+                    _ => Value::Unknown,
+                });
+            }
+            i = end + 1;
+        }
+        return Some((Value::Array(items), close + 1));
+    }
+
+    if first.is_punct("{") {
+        let close = close_of(tokens, 0)?;
+        return Some((Value::Object, close + 1));
+    }
+
+    None
+}
+
+fn number(text: &str, negative: bool) -> Value {
+    let lower = text.to_ascii_lowercase();
+    let radix = ["0x", "0o", "0b"].iter().any(|p| lower.starts_with(p));
+    let float_suffix = lower.ends_with("f32") || lower.ends_with("f64");
+    Value::Number {
+        integer: radix || !(lower.contains('.') || lower.contains('e') || float_suffix),
+        negative,
+        text: text.to_string(),
+    }
+}
+
+/// Index of the bracket closing the one at `open`
+fn close_of(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if token.kind != TokenKind::Punct {
+            continue;
+        }
+        match token.text.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Index of the first comma outside brackets
+fn top_level_comma(tokens: &[Token]) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.text.as_str() {
+            "(" | "[" | "{" if token.kind == TokenKind::Punct => depth += 1,
+            ")" | "]" | "}" if token.kind == TokenKind::Punct => depth = depth.saturating_sub(1),
+            "," if depth == 0 && token.kind == TokenKind::Punct => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,146 @@
+// Member usage
+// Finds every place the component's props, state and methods are used: template expressions,
+// method bodies (`this.name` or `self.name`) and `v-bind()` calls in styles
+
+use super::expression::{expressions, ExpressionKind};
+use super::script::{access, substitutions, tokenize, Access, Token, TokenKind};
+use super::{SectionKind, SourceFile, Span};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Where a member is used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UseSite {
+    /// In a template expression of the given kind
+    Template(ExpressionKind),
+    /// In the body of the method at this index in [`Component::methods`](super::component::Component::methods)
+    Method(usize),
+    /// In a `v-bind()` call in the style section
+    Style,
+}
+
+/// Shorthand property a use appears in, where renaming the member must keep the other half
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shorthand {
+    /// `{ label }` in a template object literal: the key stays `label`, the value is the member
+    Property,
+    /// `const { label } = this`: the key is the member, the local variable stays `label`
+    Destructuring,
+}
+
+/// A use of a component member
+#[derive(Debug, Clone)]
+pub struct MemberUse {
+    /// Member name
+    pub name: String,
+    /// Span of the name at the use
+    pub span: Span,
+    /// Whether the member is read or assigned
+    pub access: Access,
+    /// Where the use is
+    pub site: UseSite,
+    /// Shorthand property the use appears in, if any
+    pub shorthand: Option<Shorthand>,
+}
+
+/// Find every use of a component member in the file
+///
+/// Template uses are free identifiers (loop variables are excluded), so they may include
+/// names the component doesn't declare.
+pub fn member_uses(source: &SourceFile) -> Vec<MemberUse> {
+    let mut uses = vec![];
+
+    for expression in expressions(source) {
+        let kind = expression.kind;
+        uses.extend(expression.references().into_iter().map(|r| MemberUse {
+            name: r.name,
+            span: r.span,
+            access: r.access,
+            site: UseSite::Template(kind),
+            shorthand: r.shorthand.then_some(Shorthand::Property),
+        }));
+    }
+
+    if let Some(component) = source.component() {
+        for (index, method) in component.methods.iter().enumerate() {
+            let tokens = tokenize(source.slice(method.body), method.body.start);
+            method_uses(&tokens, index, &mut uses);
+        }
+    }
+
+    if let Some(style) = source.section(SectionKind::Style) {
+        static V_BIND: OnceLock<Regex> = OnceLock::new();
+        let v_bind = V_BIND.get_or_init(|| {
+            Regex::new(r#"v-bind\(\s*['"]?([A-Za-z_$][\w$]*)"#).expect("valid regex")
+        });
+        for captures in v_bind.captures_iter(source.slice(style.content)) {
+            let name = captures.get(1).expect("group 1 always matches");
+            uses.push(MemberUse {
+                name: name.as_str().to_string(),
+                span: Span::new(
+                    style.content.start + name.start(),
+                    style.content.start + name.end(),
+                ),
+                access: Access::Read,
+                site: UseSite::Style,
+                shorthand: None,
+            });
+        }
+    }
+
+    uses
+}
+
+/// Find the uses of members in the tokens of the method at `index`, including the `${...}`
+/// substitutions of its template literals
+fn method_uses(tokens: &[Token], index: usize, uses: &mut Vec<MemberUse>) {
+    for (i, token) in tokens.iter().enumerate() {
+        let is_receiver = token.is_ident("this") || token.is_ident("self");
+        let Some(name) = tokens.get(i + 2).filter(|name| {
+            is_receiver && tokens[i + 1].is_punct(".") && name.kind == TokenKind::Ident
+        }) else {
+            continue;
+        };
+        uses.push(MemberUse {
+            name: name.text.clone(),
+            span: name.span,
+            access: access(tokens, i + 2),
+            site: UseSite::Method(index),
+            shorthand: None,
+        });
+    }
+
+    // Destructuring: `const { label, count } = this;`
+    for (i, token) in tokens.iter().enumerate() {
+        let destructures = token.is_punct("}")
+            && tokens.get(i + 1).is_some_and(|t| t.is_punct("="))
+            && tokens
+                .get(i + 2)
+                .is_some_and(|t| t.is_ident("this") || t.is_ident("self"));
+        if !destructures {
+            continue;
+        }
+        let Some(open) = tokens[..i].iter().rposition(|t| t.is_punct("{")) else {
+            continue;
+        };
+        for (j, name) in tokens.iter().enumerate().take(i).skip(open + 1) {
+            // Only keys count: `{ label: text }` uses `label`, not `text`
+            let is_key = tokens[j - 1].is_punct("{") || tokens[j - 1].is_punct(",");
+            if name.kind == TokenKind::Ident && is_key {
+                uses.push(MemberUse {
+                    name: name.text.clone(),
+                    span: name.span,
+                    access: Access::Read,
+                    site: UseSite::Method(index),
+                    shorthand: (!tokens[j + 1].is_punct(":")).then_some(Shorthand::Destructuring),
+                });
+            }
+        }
+    }
+
+    for token in tokens {
+        for part in substitutions(token) {
+            method_uses(&part, index, uses);
+        }
+    }
+}
@@ -0,0 +1,357 @@
+// Rule testing harness
+// `RuleTester` runs a single rule on inline sources or fixture files annotated with the
+// diagnostics they are expected to produce, and checks that the rule's fixes give the
+// expected output. It is used for the built-in rules and is public so plugin authors can
+// test their rules the same way.
+//
+// Expected diagnostics are written on the line after the code they point at, in whatever
+// comment syntax the section uses:
+//
+//   <button @click="save">Save</button>
+//   <!-- ^^^^^^ error: no-unknown-handler: Unknown handler 'save' -->
+//
+//   let total = count + 1;
+//   //  ^^^^^ warning: unused-state
+//
+// The first caret marks the expected column; `@<column>` can be used instead when the
+// comment cannot reach that far left. The message is optional. Annotation lines are
+// removed before the rule runs, so they never shift reported positions.
+
+use crate::fix::apply_fixes;
+use crate::reporter::Issue;
+use crate::rules::Rule;
+use crate::source::{SectionKind, SourceFile};
+use std::path::{Path, PathBuf};
+
+/// Environment variable that makes [`RuleTester::fixtures`] rewrite fixtures from the actual output
+pub const UPDATE_FIXTURES_ENV: &str = "ORLINT_UPDATE_FIXTURES";
+
+/// Fixture directory of a rule: `tests/fixtures/<rule>` in the package under test
+pub fn fixture_dir(rule: &str) -> PathBuf {
+    // Cargo sets CARGO_MANIFEST_DIR when it runs tests; otherwise they run from the package root
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join("tests")
+        .join("fixtures")
+        .join(rule)
+}
+
+/// Test harness for a single rule
+pub struct RuleTester<R: Rule> {
+    rule: R,
+    file_name: String,
+}
+
+/// A diagnostic expected by an annotation
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Expected {
+    line: usize,
+    column: usize,
+    severity: String,
+    rule: String,
+    message: Option<String>,
+}
+
+impl<R: Rule> RuleTester<R> {
+    /// Create a tester for a rule
+    pub fn new(rule: R) -> Self {
+        Self {
+            rule,
+            file_name: "Test.orbit".to_string(),
+        }
+    }
+
+    /// Set the file name passed to the rule (defaults to `Test.orbit`)
+    pub fn with_file_name(mut self, file_name: &str) -> Self {
+        self.file_name = file_name.to_string();
+        self
+    }
+
+    /// Run the rule on an unannotated source
+    pub fn run(&self, source: &str) -> Vec<Issue> {
+        self.try_run(source).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Assert that the rule reports nothing for a source
+    pub fn valid(&self, source: &str) -> &Self {
+        self.invalid(source)
+    }
+
+    /// Assert that the rule reports exactly the annotated diagnostics
+    pub fn invalid(&self, annotated: &str) -> &Self {
+        if let Err(e) = self.check_annotations(annotated) {
+            panic!("{e}");
+        }
+        self
+    }
+
+    /// Assert that applying all of the rule's fixes turns `annotated` into `expected`
+    ///
+    /// Annotations are stripped from the input first; `expected` must not contain any.
+    pub fn fixes(&self, annotated: &str, expected: &str) -> &Self {
+        if let Err(e) = self.check_fixes(annotated, expected) {
+            panic!("{e}");
+        }
+        self
+    }
+
+    /// Render a source with the rule's diagnostics written in as annotations
+    ///
+    /// The output is a valid input for [`RuleTester::invalid`], which makes it a convenient
+    /// snapshot of a rule's behaviour.
+    pub fn annotate(&self, source: &str) -> String {
+        let (source, _) = parse_annotations(source);
+        let issues = self.run(&source);
+        render_annotations(&source, &issues)
+    }
+
+    /// Check every `*.orbit` fixture in a directory
+    ///
+    /// Each fixture is checked like [`RuleTester::invalid`]. If `<name>.fixed.orbit` exists
+    /// next to `<name>.orbit`, the fixes are checked against it too. Setting
+    /// `ORLINT_UPDATE_FIXTURES=1` rewrites the fixtures (and fixed files, for rules with
+    /// fixes) from the rule's actual output instead.
+    pub fn fixtures(&self, dir: impl AsRef<Path>) -> &Self {
+        let dir = dir.as_ref();
+        let update = std::env::var_os(UPDATE_FIXTURES_ENV).is_some_and(|v| v != "0");
+
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .unwrap_or_else(|e| panic!("failed to read fixtures in {}: {e}", dir.display()))
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                let name = path.to_string_lossy();
+                name.ends_with(".orbit") && !name.ends_with(".fixed.orbit")
+            })
+            .collect();
+        paths.sort();
+        assert!(!paths.is_empty(), "no fixtures found in {}", dir.display());
+
+        let mut failures = vec![];
+        for path in paths {
+            let annotated = std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+            let fixed_path = path.with_extension("fixed.orbit");
+
+            let result = if update {
+                self.update_fixture(&path, &fixed_path, &annotated)
+            } else {
+                self.check_annotations(&annotated).and_then(|()| {
+                    match std::fs::read_to_string(&fixed_path) {
+                        Ok(expected) => self.check_fixes(&annotated, &expected),
+                        Err(_) => Ok(()),
+                    }
+                })
+            };
+
+            if let Err(e) = result {
+                failures.push(format!("{}:\n{e}", path.display()));
+            }
+        }
+
+        if !failures.is_empty() {
+            panic!(
+                "{}\n\n(set {UPDATE_FIXTURES_ENV}=1 to update the fixtures)",
+                failures.join("\n\n")
+            );
+        }
+        self
+    }
+
+    /// Check the fixtures in `tests/fixtures/<rule name>`, see [`fixture_dir`] and [`RuleTester::fixtures`]
+    pub fn fixtures_for(&self) -> &Self {
+        self.fixtures(fixture_dir(self.rule.name()))
+    }
+
+    fn try_run(&self, source: &str) -> Result<Vec<Issue>, String> {
+        // Parse the same way `Linter::lint` does, so sources the linter rejects fail here too
+        let ast = crate::parser::parse_orbit_file(source, &self.file_name)
+            .map_err(|e| format!("failed to parse test source: {e}"))?;
+        let source = SourceFile::new(source);
+        self.rule
+            .check_source(&ast, &source, &self.file_name)
+            .map_err(|e| format!("rule '{}' failed: {e}", self.rule.name()))
+    }
+
+    fn check_annotations(&self, annotated: &str) -> Result<(), String> {
+        let (source, mut expected) = parse_annotations(annotated);
+        let issues = self.try_run(&source)?;
+
+        let mut actual: Vec<Expected> = issues
+            .iter()
+            .map(|issue| Expected {
+                line: issue.line,
+                column: issue.column,
+                severity: issue.severity.to_string(),
+                rule: issue.rule.clone(),
+                message: Some(issue.message.clone()),
+            })
+            .collect();
+        actual.sort();
+        expected.sort();
+
+        // Annotations without a message match any message
+        let matches = actual.len() == expected.len()
+            && actual.iter().zip(&expected).all(|(actual, expected)| {
+                Expected {
+                    message: expected.message.clone().or(actual.message.clone()),
+                    ..expected.clone()
+                } == *actual
+            });
+
+        if matches {
+            Ok(())
+        } else {
+            Err(format!(
+                "rule '{}' reported different diagnostics\n\n--- expected\n{}\n--- actual\n{}",
+                self.rule.name(),
+                annotated.trim_end(),
+                render_annotations(&source, &issues).trim_end()
+            ))
+        }
+    }
+
+    fn check_fixes(&self, annotated: &str, expected: &str) -> Result<(), String> {
+        let (source, _) = parse_annotations(annotated);
+        let issues = self.try_run(&source)?;
+        let (fixed, _) = apply_fixes(&source, &issues);
+
+        if fixed == expected {
+            Ok(())
+        } else {
+            Err(format!(
+                "fixes for rule '{}' produced different output\n\n--- expected\n{}\n--- actual\n{}",
+                self.rule.name(),
+                expected.trim_end(),
+                fixed.trim_end()
+            ))
+        }
+    }
+
+    fn update_fixture(
+        &self,
+        path: &Path,
+        fixed_path: &Path,
+        annotated: &str,
+    ) -> Result<(), String> {
+        let (source, _) = parse_annotations(annotated);
+        let issues = self.try_run(&source)?;
+        let write = |path: &Path, content: &str| {
+            std::fs::write(path, content)
+                .map_err(|e| format!("failed to write {}: {e}", path.display()))
+        };
+
+        write(path, &render_annotations(&source, &issues))?;
+        let (fixed, applied) = apply_fixes(&source, &issues);
+        if applied > 0 {
+            write(fixed_path, &fixed)?;
+        }
+        Ok(())
+    }
+}
+
+/// Split an annotated source into the plain source and the expected diagnostics
+fn parse_annotations(annotated: &str) -> (String, Vec<Expected>) {
+    let mut source = String::with_capacity(annotated.len());
+    let mut expected = vec![];
+    let mut line = 0;
+
+    for text in annotated.split_inclusive('\n') {
+        match parse_annotation(text.trim_end_matches(['\n', '\r'])) {
+            Some((column, severity, rule, message)) if line > 0 => expected.push(Expected {
+                line,
+                column,
+                severity,
+                rule,
+                message,
+            }),
+            _ => {
+                source.push_str(text);
+                line += 1;
+            }
+        }
+    }
+
+    (source, expected)
+}
+
+/// Parse an annotation line into (column, severity, rule, message)
+fn parse_annotation(line: &str) -> Option<(usize, String, String, Option<String>)> {
+    let trimmed = line.trim();
+    let inner = [("<!--", "-->"), ("/*", "*/"), ("//", "")]
+        .iter()
+        .find_map(|(open, close)| trimmed.strip_prefix(open)?.strip_suffix(close))?
+        .trim();
+
+    let (column, rest) = if let Some(rest) = inner.strip_prefix('@') {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        (rest[..digits].parse().ok()?, &rest[digits..])
+    } else if inner.starts_with('^') {
+        let caret = line.find('^')?;
+        (
+            line[..caret].chars().count() + 1,
+            inner.trim_start_matches('^'),
+        )
+    } else {
+        return None;
+    };
+
+    let mut parts = rest.trim().splitn(3, ':');
+    let severity = parts.next()?.trim().to_lowercase();
+    if !["error", "warning", "info"].contains(&severity.as_str()) {
+        return None;
+    }
+    let rule = parts.next()?.trim().to_string();
+    let message = parts.next().map(|m| m.trim().to_string());
+
+    Some((column, severity, rule, message))
+}
+
+/// Write issues into a source as annotations, using each section's comment syntax
+fn render_annotations(source: &str, issues: &[Issue]) -> String {
+    let file = SourceFile::new(source);
+    let mut issues: Vec<&Issue> = issues.iter().collect();
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+
+    let mut output = String::with_capacity(source.len());
+    let mut offset = 0;
+    for (index, text) in source.split_inclusive('\n').enumerate() {
+        output.push_str(text);
+        if !text.ends_with('\n') {
+            output.push('\n');
+        }
+
+        let kind = file
+            .sections()
+            .iter()
+            .find(|s| s.content.start <= offset && offset <= s.content.end)
+            .map(|s| s.kind);
+        let (open, close) = match kind {
+            Some(SectionKind::Script) => ("//", ""),
+            Some(SectionKind::Style) => ("/*", " */"),
+            _ => ("<!--", " -->"),
+        };
+
+        for issue in issues.iter().filter(|issue| issue.line == index + 1) {
+            let indent = text.len() - text.trim_start().len();
+            let indent = indent.min(issue.column.saturating_sub(1));
+            let marker = if issue.column > indent + open.len() + 1 {
+                format!("{}^", " ".repeat(issue.column - indent - open.len() - 1))
+            } else {
+                format!(" @{}", issue.column)
+            };
+            output.push_str(&format!(
+                "{}{open}{marker} {}: {}: {}{close}\n",
+                &text[..indent],
+                issue.severity,
+                issue.rule,
+                issue.message
+            ));
+        }
+        offset += text.len();
+    }
+
+    output
+}
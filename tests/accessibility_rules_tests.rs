@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use orlint::{
+        AccessibleNameRule, ClickKeyboardRule, ColorContrastRule, FormLabelRule, HeadingOrderRule,
+        HiddenFocusableRule, MissingAltRule, NoAutofocusRule, PageLandmarksRule,
+        PositiveTabindexRule, RoleAttributesRule, RuleTester, SingleH1Rule, ValidRoleRule,
+        WcagLevel,
+    };
+
+    #[test]
+    fn test_missing_alt() {
+        RuleTester::new(MissingAltRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_accessible_name() {
+        RuleTester::new(AccessibleNameRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_valid_role() {
+        RuleTester::new(ValidRoleRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_role_attributes() {
+        RuleTester::new(RoleAttributesRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_form_label() {
+        RuleTester::new(FormLabelRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_click_keyboard() {
+        RuleTester::new(ClickKeyboardRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_positive_tabindex() {
+        RuleTester::new(PositiveTabindexRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_no_autofocus() {
+        RuleTester::new(NoAutofocusRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_hidden_focusable() {
+        RuleTester::new(HiddenFocusableRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_color_contrast() {
+        RuleTester::new(ColorContrastRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_color_contrast_aaa() {
+        // White on #1a73e8 is 4.51:1, enough for AA and for large text at AAA
+        let source = "<template>\n  <section class=\"banner\">\n    <h1>Welcome</h1>\n    <p>Sign in to continue</p>\n  </section>\n</template>\n<style>\n.banner {\n  background: #1a73e8 no-repeat;\n  color: white;\n}\n</style>\n";
+        RuleTester::new(ColorContrastRule::default()).valid(source);
+
+        let issues = RuleTester::new(ColorContrastRule::new(WcagLevel::Aaa)).run(source);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 4);
+        assert!(issues[0].message.contains("WCAG AAA minimum of 7:1"));
+    }
+
+    #[test]
+    fn test_heading_order() {
+        RuleTester::new(HeadingOrderRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_single_h1() {
+        RuleTester::new(SingleH1Rule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_page_landmarks() {
+        RuleTester::new(PageLandmarksRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_page_globs() {
+        let source = "<template>\n  <div>\n    <h1>Title</h1>\n    <h1>Subtitle</h1>\n  </div>\n</template>\n";
+        let rule = || SingleH1Rule::new(vec!["src/pages/**/*.orbit".to_string()]);
+        assert_eq!(
+            RuleTester::new(rule())
+                .with_file_name("/app/src/pages/account/Settings.orbit")
+                .run(source)
+                .len(),
+            1
+        );
+        RuleTester::new(rule())
+            .with_file_name("/app/src/components/Settings.orbit")
+            .valid(source);
+
+        // Metadata opts a file out of the globs
+        let opted_out = format!("<metadata>\n  <page>false</page>\n</metadata>\n{source}");
+        RuleTester::new(rule())
+            .with_file_name("src/pages/Settings.orbit")
+            .valid(&opted_out);
+    }
+}
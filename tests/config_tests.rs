@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use orlint::{Config, Severity};
+    use orlint::{AnalyzerError, Config, CustomRuleCondition, Linter, Severity};
     use std::path::Path;
 
     #[test]
@@ -71,4 +71,107 @@ mod tests {
         assert!(config.is_rule_enabled("rule2"));
         assert!(!config.is_rule_enabled("rule3"));
     }
+
+    #[test]
+    fn test_custom_rules_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [[rules.custom]]
+            id = "no-console-log"
+            message = "Remove console.log calls"
+            regex = { section = "script", pattern = "console\\.log" }
+
+            [[rules.custom]]
+            id = "img-requires-alt"
+            severity = "error"
+            message = "Images must have an alt attribute"
+            element = { tag = "img", missing_attribute = "alt" }
+
+            [[rules.custom]]
+            id = "no-eval"
+            message = "Do not use eval"
+            forbidden_identifier = "eval"
+            "#,
+        )
+        .unwrap();
+
+        let custom = &config.rules.custom;
+        assert_eq!(custom.len(), 3);
+        assert_eq!(custom[0].severity, Severity::Warning);
+        assert!(matches!(
+            &custom[0].condition,
+            CustomRuleCondition::Regex { section: Some(s), .. } if s == "script"
+        ));
+        assert_eq!(custom[1].severity, Severity::Error);
+        assert!(matches!(
+            &custom[2].condition,
+            CustomRuleCondition::ForbiddenIdentifier(name) if name == "eval"
+        ));
+    }
+
+    #[test]
+    fn test_invalid_custom_rule_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [[rules.custom]]
+            id = "no-todo"
+            message = "Resolve TODO comments"
+            regex = { section = "script", pattern = "TODO(" }
+            "#,
+        )
+        .unwrap();
+
+        // Invalid patterns are reported when the configuration is loaded, not once per file
+        let error = config.validate().unwrap_err();
+        assert!(matches!(&error, AnalyzerError::Config(message) if message.contains("no-todo")));
+        assert!(matches!(
+            Linter::try_with_config(config),
+            Err(AnalyzerError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_rule_settings_config() {
+        let invalid = |config: Config| match config.validate() {
+            Err(AnalyzerError::Config(message)) => message,
+            other => panic!("expected a configuration error, got {other:?}"),
+        };
+
+        let mut config = Config::default();
+        config.rules.state_naming.pattern = "^[a-z".to_string();
+        assert!(invalid(config).contains("invalid pattern '^[a-z'"));
+
+        let mut config = Config::default();
+        config.rules.undefined_class.ignore = vec!["(".to_string()];
+        assert!(invalid(config).contains("invalid pattern '('"));
+
+        let mut config = Config::default();
+        config.rules.design_tokens.tokens_file = Some("missing.json".to_string());
+        assert!(invalid(config).contains("failed to read design tokens file"));
+    }
+
+    #[test]
+    fn test_design_tokens_path_relative_to_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join(".orlint.toml");
+        std::fs::write(
+            &config_path,
+            "[rules.design-tokens]\ntokens_file = \"tokens.json\"\n",
+        )
+        .unwrap();
+        // The tokens file is read when the configuration is validated
+        std::fs::write(
+            dir.path().join("tokens.json"),
+            r##"{ "color": { "primary": { "$value": "#0066cc" } } }"##,
+        )
+        .unwrap();
+
+        let config = Config::from_file(&config_path).unwrap();
+        let tokens = &config.rules.design_tokens;
+        assert_eq!(
+            tokens.tokens_file.as_deref().map(Path::new),
+            Some(dir.path().join("tokens.json").as_path())
+        );
+        assert_eq!(tokens.reference, "var(--{name})");
+    }
 }
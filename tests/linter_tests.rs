@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use orlint::{Config, Linter};
+    use orlint::{Config, CustomRuleCondition, CustomRuleConfig, Linter, Severity};
     use std::path::Path;
 
     // Helper function to get example file path
@@ -22,17 +22,31 @@ mod tests {
 
         let issues = linter.lint(&content, &file_path).unwrap();
 
-        // Filter out lifecycle-method issues for now
-        // This is a temporary fix until we resolve SDK-123 (lifecycle rule behavior)
-        let filtered_issues: Vec<_> = issues
-            .into_iter()
-            .filter(|i| i.rule != "lifecycle-method")
-            .collect();
-
-        // A well-formed component should have no non-lifecycle issues
-        assert!(
-            filtered_issues.is_empty(),
-            "Expected no issues but found: {filtered_issues:?}"
+        // The example is older than most rules, so it shows what they report on an otherwise
+        // well-formed component: all warnings, and no prop assigned in a method
+        assert!(issues.iter().all(|i| i.severity == Severity::Warning));
+        let mut found: Vec<_> = issues.iter().map(|i| (i.line, i.rule.as_str())).collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                // `class="button"` is styled by the bare `button` selector, not `.button`
+                (3, "undefined-class"),
+                // Props with defaults that aren't marked optional with `?`
+                (13, "required-prop-default-conflict"),
+                (14, "required-prop-default-conflict"),
+                // `isPrimary` is never bound to the `primary` class
+                (14, "unused-prop"),
+                (15, "required-prop-default-conflict"),
+                (16, "required-prop-default-conflict"),
+                // `lastClickTime` is set on every click but never read
+                (22, "state-mutation-outside-methods"),
+                (61, "unscoped-selector"),
+                // Neither class is applied in the template
+                (71, "unused-selector"),
+                (77, "unused-selector"),
+            ],
+            "{issues:?}"
         );
     }
 
@@ -121,24 +135,129 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // Temporarily ignoring this test until we fix the lifecycle rule behavior
-    fn test_lifecycle_method_rule() {
-        let config = Config::default();
+    fn test_declarative_rules() {
+        let mut config = Config::default();
+        config.analyzer.enabled_rules = vec!["img-requires-alt".to_string(), "no-eval".to_string()];
+        config.rules.custom = vec![
+            CustomRuleConfig {
+                id: "img-requires-alt".to_string(),
+                severity: Severity::Error,
+                message: "Images must have an alt attribute".to_string(),
+                description: None,
+                condition: CustomRuleCondition::Element {
+                    tag: "img".to_string(),
+                    attribute: None,
+                    value: None,
+                    missing_attribute: Some("alt".to_string()),
+                },
+            },
+            CustomRuleConfig {
+                id: "no-eval".to_string(),
+                severity: Severity::Warning,
+                message: "Do not use eval".to_string(),
+                description: None,
+                condition: CustomRuleCondition::ForbiddenIdentifier("eval".to_string()),
+            },
+        ];
+        config
+            .rules
+            .rule_severity
+            .insert("no-eval".to_string(), Severity::Error);
         let linter = Linter::with_config(config);
 
-        let file_path = example_path("BadComponent.orbit");
-        let content = std::fs::read_to_string(&file_path).unwrap();
+        let content = r#"
+<template>
+  <div><img src="logo.png"></div>
+</template>
 
-        let issues = linter.lint(&content, &file_path).unwrap();
+<script>
+component Logo {
+  props {
+    name: string;
+  }
+
+  run() {
+    return eval("1 + 1");
+  }
+}
+</script>
+
+<style>
+.logo {}
+</style>
+"#;
+        let mut issues = linter.lint(content, "Logo.orbit").unwrap();
+        issues.sort_by_key(|i| i.line);
 
-        // Should find a lifecycle-method issue, but currently failing
-        // This will be fixed in a future PR (SDK-123)
-        let _lifecycle_issue = issues.iter().any(|i| i.rule == "lifecycle-method");
-        // Temporarily commented out until the rule is fixed
-        // assert!(
-        //    lifecycle_issue,
-        //    "Missing lifecycle method issue: {:?}",
-        //    issues
-        // );
+        assert_eq!(issues.len(), 2, "Unexpected issues: {issues:?}");
+        assert_eq!(issues[0].rule, "img-requires-alt");
+        assert_eq!((issues[0].line, issues[0].column), (3, 8));
+        assert_eq!(issues[1].rule, "no-eval");
+        assert_eq!((issues[1].line, issues[1].column), (13, 12));
+        // Severity overrides apply to declarative rules too
+        assert_eq!(issues[1].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_lifecycle_method_rule() {
+        let content = "<template>\n  <div>Label</div>\n</template>\n\n<script>\ncomponent Label {\n}\n</script>\n";
+        let file_path = "Label.orbit";
+        let has_lifecycle_issue = |config: Config| {
+            Linter::with_config(config)
+                .lint(content, file_path)
+                .unwrap()
+                .iter()
+                .any(|i| i.rule == "lifecycle-method")
+        };
+
+        // Components without hooks are only reported when the check is enabled
+        assert!(!has_lifecycle_issue(Config::default()));
+        let mut config = Config::default();
+        config.rules.lifecycle_method.require_hook = true;
+        assert!(has_lifecycle_issue(config));
+    }
+
+    #[test]
+    fn test_min_severity() {
+        let rule = |id: &str, severity| CustomRuleConfig {
+            id: id.to_string(),
+            severity,
+            message: format!("{id} found TODO"),
+            description: None,
+            condition: CustomRuleCondition::Regex {
+                pattern: "TODO".to_string(),
+                section: None,
+            },
+        };
+        let reported = |min_severity| {
+            let mut config = Config::default();
+            config.analyzer.enabled_rules = vec![
+                "todo-error".to_string(),
+                "todo-warning".to_string(),
+                "todo-info".to_string(),
+            ];
+            config.rules.custom = vec![
+                rule("todo-error", Severity::Error),
+                rule("todo-warning", Severity::Warning),
+                rule("todo-info", Severity::Info),
+            ];
+            config.reporter.min_severity = min_severity;
+            let mut rules: Vec<_> = Linter::with_config(config)
+                .lint("<template>\n  <div>TODO</div>\n</template>\n", "Todo.orbit")
+                .unwrap()
+                .into_iter()
+                .map(|issue| issue.rule)
+                .collect();
+            rules.sort();
+            rules
+        };
+
+        // The minimum keeps issues at least as severe as itself
+        assert_eq!(reported(Severity::Error), ["todo-error"]);
+        assert_eq!(reported(Severity::Warning), ["todo-error", "todo-warning"]);
+        assert_eq!(
+            reported(Severity::Info),
+            ["todo-error", "todo-info", "todo-warning"]
+        );
     }
 }
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use orlint::{
+        EventHandlerNamingConfig, EventHandlerNamingRule, MemberNamingConfig, MethodNamingRule,
+        PropNamingRule, RuleTester, StateNamingRule,
+    };
+
+    #[test]
+    fn test_event_handler_naming() {
+        RuleTester::new(EventHandlerNamingRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_configured_handler_pattern() {
+        let source = "<template>\n  <button @click=\"save\">Save</button>\n</template>\n<script>\ncomponent Form {\n  save() {}\n}\n</script>\n";
+        RuleTester::new(
+            EventHandlerNamingRule::new(EventHandlerNamingConfig {
+                handler_pattern: "^[a-z]".to_string(),
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .valid(source);
+    }
+
+    #[test]
+    fn test_prop_naming() {
+        RuleTester::new(PropNamingRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_state_naming() {
+        RuleTester::new(StateNamingRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_method_naming() {
+        RuleTester::new(MethodNamingRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_configured_member_patterns() {
+        let source = "<template>\n  <p v-if=\"visible\">{{ page_title }}</p>\n</template>\n<script>\ncomponent Page {\n  props {\n    page_title: string;\n    visible: boolean;\n  }\n}\n</script>\n";
+        RuleTester::new(
+            PropNamingRule::new(MemberNamingConfig {
+                pattern: "^[a-z][a-z0-9_]*$".to_string(),
+                boolean_pattern: String::new(),
+            })
+            .unwrap(),
+        )
+        .valid(source);
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        // Invalid patterns are rejected when the rule is created, before any file is linted
+        let error = EventHandlerNamingRule::new(EventHandlerNamingConfig {
+            handler_pattern: "(".to_string(),
+            ..Default::default()
+        })
+        .err()
+        .unwrap();
+        assert!(error.contains("invalid pattern '('"));
+    }
+}
@@ -0,0 +1,281 @@
+#[cfg(test)]
+mod tests {
+    use orlint::plugin::{self, CheckRequest, CheckResponse, PluginDeclaration};
+    use orlint::source::SourceFile;
+    use orlint::{Issue, Rule, Severity};
+    use std::ffi::CStr;
+    use std::path::Path;
+    use tempfile::NamedTempFile;
+
+    struct NoTodoRule;
+
+    impl Rule for NoTodoRule {
+        fn name(&self) -> &str {
+            "no-todo"
+        }
+
+        fn description(&self) -> &str {
+            "TODO comments should be resolved"
+        }
+
+        fn check_source(
+            &self,
+            _ast: &orbit::parser::OrbitAst,
+            source: &SourceFile,
+            file_path: &str,
+        ) -> Result<Vec<Issue>, String> {
+            Ok(source
+                .text()
+                .match_indices("TODO")
+                .map(|(offset, _)| {
+                    let (line, column) = source.position(offset);
+                    Issue {
+                        rule: self.name().to_string(),
+                        message: "Unresolved TODO".to_string(),
+                        file: file_path.to_string(),
+                        line,
+                        column,
+                        severity: Severity::Warning,
+                        fix: None,
+                    }
+                })
+                .collect())
+        }
+    }
+
+    fn rules() -> plugin::export::Rules {
+        vec![Box::new(NoTodoRule)]
+    }
+
+    #[test]
+    fn test_missing_plugin_library() {
+        let err = plugin::load_plugin(Path::new("does/not/exist/libmissing.so"))
+            .err()
+            .expect("loading a missing library should fail");
+        assert!(err.to_string().contains("libmissing.so"), "{err}");
+    }
+
+    #[test]
+    fn test_not_a_plugin() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("examples")
+            .join("Button.orbit");
+        assert!(plugin::load_plugin(&path).is_err());
+    }
+
+    #[test]
+    fn test_plugin_declaration() {
+        let ptr = plugin::export::declare("acme-rules", &rules());
+        let json = unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { plugin::export::free(ptr) };
+
+        let declaration: PluginDeclaration = serde_json::from_str(&json).unwrap();
+        assert_eq!(declaration.name, "acme-rules");
+        assert_eq!(declaration.orlint_version, orlint::VERSION);
+        assert_eq!(declaration.rules.len(), 1);
+        assert_eq!(declaration.rules[0].name, "no-todo");
+    }
+
+    #[test]
+    fn test_plugin_check_protocol() {
+        let request = serde_json::to_string(&CheckRequest {
+            file: "Todo.orbit".to_string(),
+            source: r#"
+<template>
+  <div>TODO</div>
+</template>
+
+<script>
+component Todo {
+  props {
+    name: string;
+  }
+}
+</script>
+
+<style>
+.todo {}
+</style>
+"#
+            .to_string(),
+            sections: vec![],
+            template: Default::default(),
+        })
+        .unwrap();
+
+        let response = plugin::export::check_json(&rules(), "no-todo", &request);
+        match serde_json::from_str(&response).unwrap() {
+            CheckResponse::Issues(issues) => {
+                assert_eq!(issues.len(), 1);
+                assert_eq!((issues[0].line, issues[0].column), (3, 8));
+            }
+            CheckResponse::Error(e) => panic!("unexpected error: {e}"),
+        }
+
+        let response = plugin::export::check_json(&rules(), "unknown", &request);
+        assert!(matches!(
+            serde_json::from_str(&response).unwrap(),
+            CheckResponse::Error(_)
+        ));
+    }
+
+    /// Build a WebAssembly plugin that answers every call with canned JSON
+    fn wasm_plugin(name: &str, abi_version: u32, response: &str, extra: &str) -> NamedTempFile {
+        let declaration = format!(
+            r#"{{"name":"{name}","orlint_version":"{}","rules":[{{"name":"no-todo","description":"TODO comments should be resolved"}}]}}"#,
+            orlint::VERSION
+        );
+        let escape = |json: &str| json.replace('"', "\\\"");
+        let module = format!(
+            r#"(module
+                {extra}
+                (memory (export "memory") 1)
+                (global $heap (mut i32) (i32.const 8192))
+                (data (i32.const 0) "{}")
+                (data (i32.const 4096) "{}")
+                (func (export "orlint_plugin_abi_version") (result i32) (i32.const {abi_version}))
+                (func (export "orlint_alloc") (param $len i32) (result i32) (local $ptr i32)
+                    (local.set $ptr (global.get $heap))
+                    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+                    (local.get $ptr))
+                (func (export "orlint_dealloc") (param i32 i32))
+                (func (export "orlint_plugin_declare") (result i64) (i64.const {}))
+                (func (export "orlint_plugin_check") (param i32 i32 i32 i32) (result i64)
+                    {response}))"#,
+            escape(&declaration),
+            escape(
+                r#"{"issues":[{"rule":"no-todo","message":"Unresolved TODO","file":"Todo.orbit","line":3,"column":8,"severity":"Warning"}]}"#
+            ),
+            declaration.len(),
+        );
+
+        // Removed when the returned file is dropped at the end of the test
+        let file = tempfile::Builder::new().suffix(".wasm").tempfile().unwrap();
+        std::fs::write(file.path(), wat::parse_str(module).unwrap()).unwrap();
+        file
+    }
+
+    /// Packed pointer/length of the canned issues response
+    const ISSUES_RESPONSE: &str =
+        "(i64.or (i64.shl (i64.const 4096) (i64.const 32)) (i64.const 120))";
+
+    #[test]
+    fn test_wasm_plugin() {
+        let module = wasm_plugin(
+            "wasm-rules",
+            plugin::PLUGIN_ABI_VERSION,
+            ISSUES_RESPONSE,
+            "",
+        );
+        let rules = plugin::load_plugin(module.path()).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].name(), "no-todo");
+
+        let content = "<template>\n  <div>\n  </div>\n</template>\n";
+        let ast = orlint::parser::parse_orbit_file(content, "Todo.orbit").unwrap();
+        let issues = rules[0]
+            .check_source(&ast, &SourceFile::new(content), "Todo.orbit")
+            .unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (3, 8));
+    }
+
+    #[test]
+    fn test_wasm_plugin_imports_rejected() {
+        let module = wasm_plugin(
+            "wasm-imports",
+            plugin::PLUGIN_ABI_VERSION,
+            ISSUES_RESPONSE,
+            r#"(import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32)))"#,
+        );
+        let err = plugin::load_plugin(module.path())
+            .err()
+            .expect("imports must be rejected");
+        assert!(
+            err.to_string().contains("may not import host functions"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_wasm_plugin_abi_mismatch() {
+        let module = wasm_plugin(
+            "wasm-abi",
+            plugin::PLUGIN_ABI_VERSION + 1,
+            ISSUES_RESPONSE,
+            "",
+        );
+        let err = plugin::load_plugin(module.path())
+            .err()
+            .expect("ABI mismatch must fail");
+        assert!(err.to_string().contains("incompatible"), "{err}");
+    }
+
+    #[test]
+    fn test_wasm_plugin_runaway_loop() {
+        let module = wasm_plugin(
+            "wasm-loop",
+            plugin::PLUGIN_ABI_VERSION,
+            "(loop $spin (br $spin)) (unreachable)",
+            "",
+        );
+        let rules = plugin::load_plugin(module.path()).unwrap();
+        let content = "<template><div></div></template>";
+        let ast = orlint::parser::parse_orbit_file(content, "Loop.orbit").unwrap();
+        assert!(rules[0]
+            .check_source(&ast, &SourceFile::new(content), "Loop.orbit")
+            .is_err());
+    }
+
+    #[test]
+    fn test_wasm_plugin_string_out_of_bounds() {
+        let responses = [
+            // Larger than the string limit
+            (
+                "(i64.or (i64.shl (i64.const 4096) (i64.const 32)) (i64.const 0xFFFFFFFF))",
+                "exceeds the limit",
+            ),
+            // Past the end of the plugin's single page of memory
+            (
+                "(i64.or (i64.shl (i64.const 65500) (i64.const 32)) (i64.const 120))",
+                "outside the plugin's memory",
+            ),
+        ];
+        for (response, expected) in responses {
+            let module = wasm_plugin("wasm-bounds", plugin::PLUGIN_ABI_VERSION, response, "");
+            let rules = plugin::load_plugin(module.path()).unwrap();
+            let content = "<template><div></div></template>";
+            let ast = orlint::parser::parse_orbit_file(content, "Bounds.orbit").unwrap();
+            let err = rules[0]
+                .check_source(&ast, &SourceFile::new(content), "Bounds.orbit")
+                .expect_err("a string outside the plugin's memory must be rejected");
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_wasm_plugin_reinstantiated_after_trap() {
+        // Traps on every call after the first one made on the same instance
+        let module = wasm_plugin(
+            "wasm-trap",
+            plugin::PLUGIN_ABI_VERSION,
+            &format!(
+                "(global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+                (if (i32.gt_u (global.get $calls) (i32.const 1)) (then (unreachable)))
+                {ISSUES_RESPONSE}"
+            ),
+            "(global $calls (mut i32) (i32.const 0))",
+        );
+        let rules = plugin::load_plugin(module.path()).unwrap();
+        let content = "<template>\n  <div>\n  </div>\n</template>\n";
+        let ast = orlint::parser::parse_orbit_file(content, "Todo.orbit").unwrap();
+        let check = || rules[0].check_source(&ast, &SourceFile::new(content), "Todo.orbit");
+
+        assert!(check().is_ok());
+        assert!(check().is_err());
+        // The trapped instance was dropped, so this call starts from a fresh one
+        assert_eq!(check().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use orlint::source::SourceFile;
+    use orlint::Query;
+
+    const CONTENT: &str = r#"
+<template>
+  <form class="login">
+    <label for="user">User</label>
+    <input id="user" :value="username">
+    <button @click="handleSubmit" aria-label="Submit"></button>
+    <button @click="handleCancel"></button>
+    <div><button type="reset">Reset</button></div>
+  </form>
+</template>
+"#;
+
+    fn find_tags(pattern: &str) -> Vec<(String, usize)> {
+        let source = SourceFile::new(CONTENT);
+        let query = Query::parse(pattern).unwrap();
+        query
+            .find(source.template())
+            .iter()
+            .map(|m| {
+                (
+                    m.element.tag.clone(),
+                    source.position(m.element.span.start).0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_attribute_and_pseudo_classes() {
+        assert_eq!(
+            find_tags("button:not([aria-label]):not(:has(text))"),
+            vec![("button".to_string(), 7)]
+        );
+        assert_eq!(find_tags("input[id=user]").len(), 1);
+        assert_eq!(find_tags("[@click^=handle]").len(), 2);
+        assert_eq!(find_tags("button:empty").len(), 2);
+    }
+
+    #[test]
+    fn test_combinators() {
+        assert_eq!(find_tags("form > button").len(), 2);
+        assert_eq!(find_tags("form button").len(), 3);
+        assert_eq!(
+            find_tags("form:has(> div button)"),
+            vec![("form".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    fn test_captures() {
+        let source = SourceFile::new(CONTENT);
+        let query = Query::parse("form$form > button[@click=$handler]").unwrap();
+        let matches = query.find(source.template());
+
+        let handlers: Vec<_> = matches
+            .iter()
+            .map(|m| m.capture("handler").unwrap().text.as_str())
+            .collect();
+        assert_eq!(handlers, vec!["handleSubmit", "handleCancel"]);
+        assert_eq!(matches[0].capture("form").unwrap().text, "form");
+    }
+
+    #[test]
+    fn test_invalid_query() {
+        assert!(Query::parse("button:hover").is_err());
+        assert!(Query::parse("button[").is_err());
+        assert!(Query::parse("a >").is_err());
+
+        // Text nodes are only visited by `:has()`, as the last part of its selector
+        assert!(Query::parse("text").is_err());
+        assert!(Query::parse("div > text").is_err());
+        assert!(Query::parse("button:not(text)").is_err());
+        assert!(Query::parse("div:has(text span)").is_err());
+        assert!(Query::parse("div:has(text[title])").is_err());
+        assert!(Query::parse("p:has(> text)").is_ok());
+        assert!(Query::parse("button:not(:has(text))").is_ok());
+    }
+}
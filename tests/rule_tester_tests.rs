@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use orlint::source::{SourceFile, Span};
+    use orlint::{Edit, Fix, Issue, Rule, RuleTester, Severity};
+
+    /// Flags `TODO` markers and fixes them by replacing them with `DONE`
+    struct NoTodoRule;
+
+    impl Rule for NoTodoRule {
+        fn name(&self) -> &str {
+            "no-todo"
+        }
+
+        fn description(&self) -> &str {
+            "TODO comments should be resolved"
+        }
+
+        fn check_source(
+            &self,
+            _ast: &orbit::parser::OrbitAst,
+            source: &SourceFile,
+            file_path: &str,
+        ) -> Result<Vec<Issue>, String> {
+            Ok(source
+                .text()
+                .match_indices("TODO")
+                .map(|(offset, todo)| {
+                    let (line, column) = source.position(offset);
+                    Issue {
+                        rule: self.name().to_string(),
+                        message: "Unresolved TODO".to_string(),
+                        file: file_path.to_string(),
+                        line,
+                        column,
+                        severity: Severity::Warning,
+                        fix: Some(Fix::new(
+                            "Mark as done",
+                            vec![Edit::replace(
+                                Span::new(offset, offset + todo.len()),
+                                "DONE",
+                            )],
+                        )),
+                    }
+                })
+                .collect())
+        }
+    }
+
+    const ANNOTATED: &str = r#"<template>
+  <div>TODO</div>
+  <!-- ^ warning: no-todo: Unresolved TODO -->
+</template>
+
+<script>
+component Todo {
+  state {
+    // TODO: remove
+    // ^ warning: no-todo
+    count: i32 = 0;
+  }
+}
+</script>
+"#;
+
+    #[test]
+    fn test_annotated_diagnostics() {
+        RuleTester::new(NoTodoRule)
+            .invalid(ANNOTATED)
+            .valid("<template>\n  <div>Done</div>\n</template>\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "reported different diagnostics")]
+    fn test_wrong_position_fails() {
+        RuleTester::new(NoTodoRule).invalid(
+            "<template>\n  <div>TODO</div>\n  <!-- @3 warning: no-todo -->\n</template>\n",
+        );
+    }
+
+    #[test]
+    fn test_fixes() {
+        RuleTester::new(NoTodoRule).fixes(
+            ANNOTATED,
+            &ANNOTATED
+                .lines()
+                .filter(|line| !line.contains('^'))
+                .map(|line| format!("{}\n", line.replace("TODO", "DONE")))
+                .collect::<String>(),
+        );
+    }
+
+    #[test]
+    fn test_annotate_round_trip() {
+        let tester = RuleTester::new(NoTodoRule);
+        let source = "<template>\nTODO\n</template>\n<style>\n  /* TODO */\n</style>\n";
+        let annotated = tester.annotate(source);
+
+        assert_eq!(
+            annotated,
+            "<template>\nTODO\n<!-- @1 warning: no-todo: Unresolved TODO -->\n</template>\n\
+             <style>\n  /* TODO */\n  /* ^ warning: no-todo: Unresolved TODO */\n</style>\n"
+        );
+        tester.invalid(&annotated);
+    }
+}
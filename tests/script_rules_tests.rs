@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use orlint::{
+        InitialValueTypeMismatchRule, NoPropMutationRule, RequiredPropDefaultConflictRule,
+        RuleTester, StateMutationOutsideMethodsRule, UnusedPropRule, UnusedStateRule,
+        ValidTypeAnnotationRule,
+    };
+    use orlint::{LifecycleConfig, LifecycleMethodRule, TypeAnnotationConfig};
+
+    #[test]
+    fn test_unused_prop() {
+        RuleTester::new(UnusedPropRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_unused_state() {
+        RuleTester::new(UnusedStateRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_no_prop_mutation() {
+        RuleTester::new(NoPropMutationRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_state_mutation_outside_methods() {
+        RuleTester::new(StateMutationOutsideMethodsRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_initial_value_type_mismatch() {
+        RuleTester::new(InitialValueTypeMismatchRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_valid_type_annotation() {
+        RuleTester::new(ValidTypeAnnotationRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_configured_known_types() {
+        let source = "<template></template>\n<script>\ncomponent Card {\n  props {\n    theme: Theme;\n  }\n}\n</script>\n";
+        assert_eq!(
+            RuleTester::new(ValidTypeAnnotationRule::default())
+                .run(source)
+                .len(),
+            1
+        );
+        RuleTester::new(ValidTypeAnnotationRule::new(TypeAnnotationConfig {
+            known_types: vec!["Theme".to_string()],
+        }))
+        .valid(source);
+    }
+
+    #[test]
+    fn test_lifecycle_method() {
+        RuleTester::new(LifecycleMethodRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_require_lifecycle_hook() {
+        let source = "<template></template>\n<script>\ncomponent Label {\n}\n</script>\n";
+        RuleTester::new(LifecycleMethodRule::default()).valid(source);
+        let issues = RuleTester::new(LifecycleMethodRule::new(LifecycleConfig {
+            require_hook: true,
+        }))
+        .run(source);
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].line, issues[0].column), (3, 11));
+    }
+
+    #[test]
+    fn test_required_prop_default_conflict() {
+        RuleTester::new(RequiredPropDefaultConflictRule).fixtures_for();
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use orlint::source::expression::expressions;
+    use orlint::source::SourceFile;
+    use std::path::Path;
+
+    fn button() -> SourceFile {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("examples")
+            .join("Button.orbit");
+        SourceFile::new(&std::fs::read_to_string(path).unwrap())
+    }
+
+    #[test]
+    fn test_component_model() {
+        let source = button();
+        let component = source.component().expect("Button declares a component");
+
+        assert_eq!(component.name, "Button");
+        let props: Vec<_> = component.props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(props, ["label", "isPrimary", "isDisabled", "onClick"]);
+        assert_eq!(
+            component
+                .prop("onClick")
+                .unwrap()
+                .type_annotation
+                .as_ref()
+                .unwrap()
+                .text,
+            "() => void"
+        );
+        assert_eq!(
+            component
+                .state_variable("lastClickTime")
+                .unwrap()
+                .type_annotation
+                .as_ref()
+                .unwrap()
+                .text,
+            "number | null"
+        );
+
+        let methods: Vec<_> = component.methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(
+            methods,
+            ["mounted", "updated", "handleClick", "getClickCount"]
+        );
+        let accessor = component.method("getClickCount").unwrap();
+        assert_eq!(accessor.return_type.as_ref().unwrap().text, "number");
+        assert!(source
+            .slice(accessor.body)
+            .contains("return this.clickCount;"));
+    }
+
+    #[test]
+    fn test_template_references() {
+        let source = button();
+        let names: Vec<_> = expressions(&source)
+            .iter()
+            .flat_map(|e| e.references())
+            .map(|r| r.name)
+            .collect();
+        assert_eq!(names, ["handleClick", "label"]);
+    }
+
+    #[test]
+    fn test_stylesheet() {
+        let source = SourceFile::new(
+            r#"<template>
+  <div class="card"><h2 class="card__title">Title</h2><p>Text</p></div>
+</template>
+
+<style lang="scss">
+/* Card */
+.card {
+  color: #333;
+  &__title { font-weight: bold !important; }
+  > p:not(.muted) { margin: 0 }
+  @media (max-width: 600px) { padding: 0 }
+}
+</style>
+"#,
+        );
+        let rules = &source.stylesheet().rules;
+        let selectors: Vec<_> = rules.iter().map(|r| r.selectors[0].text.as_str()).collect();
+        assert_eq!(selectors, [".card", "&__title", "> p:not(.muted)", ".card"]);
+        assert_eq!(rules[0].declarations[0].property, "color");
+        assert_eq!(source.slice(rules[0].declarations[0].value_span), "#333");
+        assert!(rules[1].declarations[0].important);
+        assert_eq!(rules[1].declarations[0].value, "bold");
+        assert_eq!(rules[3].conditions, ["@media (max-width: 600px)"]);
+
+        let title = rules[1].selectors[0].class_names()[0];
+        assert_eq!(title.name, "card__title");
+        assert_eq!(source.slice(title.span), "&__title");
+        assert_eq!(rules[2].selectors[0].specificity(), (0, 2, 1));
+
+        let elements = source.template().elements();
+        let (card, heading, paragraph) = (elements[0], elements[1], elements[2]);
+        assert!(rules[0].selectors[0].matches(card, &[]));
+        assert!(rules[1].selectors[0].matches(heading, &[card]));
+        assert!(rules[2].selectors[0].matches(paragraph, &[card]));
+        assert!(!rules[2].selectors[0].matches(heading, &[card]));
+    }
+}
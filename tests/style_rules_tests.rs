@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use orlint::testing::fixture_dir;
+    use orlint::{
+        AnalyzerError, Config, DesignTokensConfig, DesignTokensRule, InvalidValueRule, Linter,
+        NoDuplicateDeclarationsRule, NoDuplicateSelectorsRule, NoImportantRule, RuleTester,
+        UndefinedClassConfig, UndefinedClassRule, UnknownPropertyConfig, UnknownPropertyRule,
+        UnscopedSelectorConfig, UnscopedSelectorRule, UnusedSelectorRule,
+    };
+
+    #[test]
+    fn test_unused_selector() {
+        RuleTester::new(UnusedSelectorRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_undefined_class() {
+        RuleTester::new(UndefinedClassRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_undefined_class_ignore() {
+        let rule = UndefinedClassRule::new(UndefinedClassConfig {
+            ignore: vec!["^u-".to_string(), "^card$".to_string()],
+        })
+        .unwrap();
+        let issues = RuleTester::new(rule).run(
+            r#"<template>
+  <div class="card u-bold title">Title</div>
+</template>
+
+<style>
+.title { color: red; }
+</style>
+"#,
+        );
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn test_unscoped_selector() {
+        RuleTester::new(UnscopedSelectorRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_unscoped_selector_scoped_styles() {
+        let rule = UnscopedSelectorRule::new(UnscopedSelectorConfig { scoped: true });
+        let issues = RuleTester::new(rule).run(
+            r#"<template>
+  <div class="card"><p>Text</p></div>
+</template>
+
+<style>
+p { margin: 0; }
+:root { --gap: 8px; }
+</style>
+"#,
+        );
+        let messages: Vec<_> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["Selector ':root' styles the whole document rather than this component; move it to a global stylesheet"]
+        );
+    }
+
+    #[test]
+    fn test_no_duplicate_selectors() {
+        RuleTester::new(NoDuplicateSelectorsRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_no_duplicate_declarations() {
+        RuleTester::new(NoDuplicateDeclarationsRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_no_important() {
+        RuleTester::new(NoImportantRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_unknown_property() {
+        RuleTester::new(UnknownPropertyRule::default()).fixtures_for();
+    }
+
+    #[test]
+    fn test_unknown_property_known_properties() {
+        let rule = UnknownPropertyRule::new(UnknownPropertyConfig {
+            known_properties: vec!["canvas-composite".to_string()],
+        });
+        RuleTester::new(rule).valid(
+            r#"<style>
+.layer { canvas-composite: multiply; }
+</style>
+"#,
+        );
+    }
+
+    #[test]
+    fn test_invalid_value() {
+        RuleTester::new(InvalidValueRule).fixtures_for();
+    }
+
+    fn tokens_rule(file: &str) -> DesignTokensRule {
+        DesignTokensRule::new(DesignTokensConfig {
+            tokens_file: Some(
+                fixture_dir("design-tokens")
+                    .join(file)
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            ..DesignTokensConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_design_tokens() {
+        RuleTester::new(tokens_rule("tokens.json")).fixtures_for();
+    }
+
+    #[test]
+    fn test_design_tokens_toml() {
+        RuleTester::new(tokens_rule("tokens.toml")).fixes(
+            "<style>\n.card { padding: 16px; color: #0066cc; }\n</style>\n",
+            "<style>\n.card { padding: var(--spacing-md); color: var(--color-primary); }\n</style>\n",
+        );
+    }
+
+    #[test]
+    fn test_design_tokens_missing_file() {
+        // The token file is loaded once when the linter is created, not on every lint
+        let mut config = Config::default();
+        config.rules.design_tokens.tokens_file = Some("missing.json".to_string());
+        match Linter::try_with_config(config) {
+            Err(AnalyzerError::Config(message)) => {
+                assert!(message.contains("failed to read design tokens file"))
+            }
+            _ => panic!("expected a configuration error"),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod tests {
+    use orlint::{
+        NoDuplicateIdsRule, RuleTester, UndefinedEventHandlerRule, UndefinedTemplateReferenceRule,
+    };
+
+    #[test]
+    fn test_undefined_template_reference() {
+        RuleTester::new(UndefinedTemplateReferenceRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_undefined_event_handler() {
+        RuleTester::new(UndefinedEventHandlerRule).fixtures_for();
+    }
+
+    #[test]
+    fn test_no_duplicate_ids() {
+        RuleTester::new(NoDuplicateIdsRule).fixtures_for();
+    }
+}